@@ -1,5 +1,7 @@
 use std::collections::HashSet;
 
+use day_02::{parse_game, Game};
+
 fn main() {
     // https://adventofcode.com/2023/day/2
     println!("Hello, world!");
@@ -10,7 +12,7 @@ fn main() {
     let games: Vec<Game> = input1
         .split("\n")
         .map(|s| s.trim())
-        .map(|s| parse_game(s))
+        .map(|s| parse_game(s).unwrap())
         .collect();
 
     let analysis = analyze_games(games, 12, 13, 14);
@@ -74,74 +76,11 @@ fn analyze_games(
     return analysis;
 }
 
-#[derive(Debug, PartialEq)]
-struct CubeSet {
-    red: u32,
-    blue: u32,
-    green: u32,
-}
-
-#[derive(Debug, PartialEq)]
-struct Game {
-    id: u32,
-    sets: Vec<CubeSet>,
-}
-
-fn parse_game(input: &str) -> Game {
-    let colon_parts: Vec<&str> = input.split(":").collect();
-    // left contains the game id
-    let left = colon_parts.first().unwrap().trim();
-
-    // right contains the sets
-    let right = colon_parts.last().unwrap().trim();
-
-    println!("left = {:#?}", left);
-    println!("right = {:#?}", right);
-
-    let game_id_str = left.to_lowercase().replace("game ", "");
-    let game_id = game_id_str.parse::<u32>().unwrap();
-
-    println!("game id = {:#?}", game_id);
-
-    let sets: Vec<&str> = right.split(";").map(|s| s.trim()).collect();
-
-    let game_sets: Vec<CubeSet> = sets.into_iter().map(|d| parse_set(d)).collect();
-
-    return Game {
-        id: game_id,
-        sets: game_sets,
-    };
-}
-
-fn parse_set(input: &str) -> CubeSet {
-    let parts: Vec<&str> = input.split(",").map(|s| s.trim()).collect();
-    // per part use a conditional to figure out which one it is?
-    let mut set = CubeSet {
-        red: 0,
-        blue: 0,
-        green: 0,
-    };
-    for part in parts.into_iter() {
-        if part.contains("green") {
-            let g = part.replace("green", "").trim().parse::<u32>().unwrap();
-            set.green = g;
-        } else if part.contains("blue") {
-            let b = part.replace("blue", "").trim().parse::<u32>().unwrap();
-            set.blue = b;
-        } else if part.contains("red") {
-            let r = part.replace("red", "").trim().parse::<u32>().unwrap();
-            set.red = r;
-        } else {
-            dbg!("an unknown cube color was found: {}", part);
-        }
-    }
-
-    return set;
-}
-
 #[cfg(test)]
 mod tests {
-    use crate::{analyze_games, parse_game, parse_set, CubeSet, Game};
+    use day_02::{parse_set, CubeSet};
+
+    use crate::{analyze_games, parse_game, Game};
 
     #[test]
     fn it_parses_game1() {
@@ -166,7 +105,7 @@ mod tests {
                 },
             ],
         };
-        let actual = parse_game(input);
+        let actual = parse_game(input).unwrap();
 
         assert_eq!(actual.id, expect.id);
 
@@ -206,7 +145,7 @@ mod tests {
                 },
             ],
         };
-        let actual = parse_game(input);
+        let actual = parse_game(input).unwrap();
 
         assert_eq!(actual.id, expect.id);
 
@@ -221,7 +160,7 @@ mod tests {
             blue: 19,
             green: 1,
         };
-        let actual = parse_set(input);
+        let actual = parse_set(input).unwrap();
         assert_eq!(actual, expect);
     }
 
@@ -237,7 +176,7 @@ mod tests {
         let games: Vec<Game> = input
             .split("\n")
             .map(|s| s.trim())
-            .map(|s| parse_game(s))
+            .map(|s| parse_game(s).unwrap())
             .collect();
 
         let analysis = analyze_games(games, 12, 13, 14);
@@ -1,5 +1,7 @@
 use std::collections::HashSet;
 
+use day_02::{parse_game, CubeSet, Game};
+
 fn main() {
     // https://adventofcode.com/2023/day/2
     println!("Hello, world!");
@@ -10,7 +12,7 @@ fn main() {
     let answer: u32 = input1
         .split("\n")
         .map(|s| s.trim())
-        .map(|s| parse_game(s))
+        .map(|s| parse_game(s).unwrap())
         .map(|game| find_fewest_signature(game))
         .sum::<u32>();
 
@@ -75,71 +77,6 @@ fn analyze_games(
     return analysis;
 }
 
-#[derive(Debug, PartialEq)]
-struct CubeSet {
-    red: u32,
-    blue: u32,
-    green: u32,
-}
-
-#[derive(Debug, PartialEq)]
-struct Game {
-    id: u32,
-    sets: Vec<CubeSet>,
-}
-
-fn parse_game(input: &str) -> Game {
-    let colon_parts: Vec<&str> = input.split(":").collect();
-    // left contains the game id
-    let left = colon_parts.first().unwrap().trim();
-
-    // right contains the sets
-    let right = colon_parts.last().unwrap().trim();
-
-    println!("left = {:#?}", left);
-    println!("right = {:#?}", right);
-
-    let game_id_str = left.to_lowercase().replace("game ", "");
-    let game_id = game_id_str.parse::<u32>().unwrap();
-
-    println!("game id = {:#?}", game_id);
-
-    let sets: Vec<&str> = right.split(";").map(|s| s.trim()).collect();
-
-    let game_sets: Vec<CubeSet> = sets.into_iter().map(|d| parse_set(d)).collect();
-
-    return Game {
-        id: game_id,
-        sets: game_sets,
-    };
-}
-
-fn parse_set(input: &str) -> CubeSet {
-    let parts: Vec<&str> = input.split(",").map(|s| s.trim()).collect();
-    // per part use a conditional to figure out which one it is?
-    let mut set = CubeSet {
-        red: 0,
-        blue: 0,
-        green: 0,
-    };
-    for part in parts.into_iter() {
-        if part.contains("green") {
-            let g = part.replace("green", "").trim().parse::<u32>().unwrap();
-            set.green = g;
-        } else if part.contains("blue") {
-            let b = part.replace("blue", "").trim().parse::<u32>().unwrap();
-            set.blue = b;
-        } else if part.contains("red") {
-            let r = part.replace("red", "").trim().parse::<u32>().unwrap();
-            set.red = r;
-        } else {
-            dbg!("an unknown cube color was found: {}", part);
-        }
-    }
-
-    return set;
-}
-
 fn find_fewest_thresholds(game: Game) -> CubeSet {
     let mut red: Vec<u32> = vec![];
     let mut green: Vec<u32> = vec![];
@@ -176,9 +113,10 @@ fn find_fewest_signature(game: Game) -> u32 {
 
 #[cfg(test)]
 mod tests {
+    use day_02::parse_set;
+
     use crate::{
-        analyze_games, find_fewest_signature, find_fewest_thresholds, parse_game, parse_set,
-        CubeSet, Game,
+        analyze_games, find_fewest_signature, find_fewest_thresholds, parse_game, CubeSet, Game,
     };
 
     #[test]
@@ -204,7 +142,7 @@ mod tests {
                 },
             ],
         };
-        let actual = parse_game(input);
+        let actual = parse_game(input).unwrap();
 
         assert_eq!(actual.id, expect.id);
 
@@ -244,7 +182,7 @@ mod tests {
                 },
             ],
         };
-        let actual = parse_game(input);
+        let actual = parse_game(input).unwrap();
 
         assert_eq!(actual.id, expect.id);
 
@@ -259,7 +197,7 @@ mod tests {
             blue: 19,
             green: 1,
         };
-        let actual = parse_set(input);
+        let actual = parse_set(input).unwrap();
         assert_eq!(actual, expect);
     }
 
@@ -269,7 +207,7 @@ mod tests {
         // In game 1, the game could have been played with as few as 4 red,
         //  2 green, and 6 blue cubes.
         //   If any color had even one fewer cube, the game would have been impossible.
-        let game = parse_game("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green");
+        let game = parse_game("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green").unwrap();
         let expect = CubeSet {
             red: 4,
             green: 2,
@@ -286,7 +224,7 @@ mod tests {
         //  2 green, and 6 blue cubes.
         //   If any color had even one fewer cube, the game would have been impossible.
         // 48
-        let game = parse_game("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green");
+        let game = parse_game("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green").unwrap();
         let expect = 48;
         let actual = find_fewest_signature(game);
         assert_eq!(actual, expect);
@@ -296,7 +234,8 @@ mod tests {
     fn it_finds_fewest_game4() {
         // Game 4 required at least 14 red, 3 green, and 15 blue cubes.
         let game =
-            parse_game("Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red");
+            parse_game("Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red")
+                .unwrap();
         let expect = CubeSet {
             red: 14,
             green: 3,
@@ -311,7 +250,8 @@ mod tests {
         // Game 4 required at least 14 red, 3 green, and 15 blue cubes.
         // 630
         let game =
-            parse_game("Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red");
+            parse_game("Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red")
+                .unwrap();
         let expect = 630;
         let actual = find_fewest_signature(game);
         assert_eq!(actual, expect);
@@ -329,7 +269,7 @@ mod tests {
         let actual: u32 = input
             .split("\n")
             .map(|s| s.trim())
-            .map(|s| parse_game(s))
+            .map(|s| parse_game(s).unwrap())
             .map(|game| find_fewest_signature(game))
             .sum::<u32>();
 
@@ -0,0 +1,413 @@
+use std::{fmt, ops::Range};
+
+// the game line grammar, whitespace is allowed between any two tokens
+//
+//   game  := "game" number ":" set ( ";" set )*
+//   set   := draw ( "," draw )*
+//   draw  := number color
+//   color := "red" | "reds" | "green" | "greens" | "blue" | "blues"
+//
+// keywords and colors are case insensitive
+
+#[derive(Debug, PartialEq)]
+pub struct CubeSet {
+    pub red: u32,
+    pub blue: u32,
+    pub green: u32,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Game {
+    pub id: u32,
+    pub sets: Vec<CubeSet>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Color {
+    Red,
+    Green,
+    Blue,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseErrorKind {
+    ExpectedGameKeyword,
+    ExpectedNumber,
+    ExpectedColon,
+    ExpectedColor,
+    ExpectedSeparator,
+    UnknownColor(String),
+    DuplicateColor(Color),
+    // a number glued to a word like "12green"
+    AmbiguousToken(String),
+    NumberOverflow,
+    UnexpectedCharacter(char),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    // byte offsets into the original line
+    pub span: Range<usize>,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match &self.kind {
+            ParseErrorKind::ExpectedGameKeyword => "expected \"Game\"".to_string(),
+            ParseErrorKind::ExpectedNumber => "expected a number".to_string(),
+            ParseErrorKind::ExpectedColon => "expected \":\"".to_string(),
+            ParseErrorKind::ExpectedColor => "expected a color".to_string(),
+            ParseErrorKind::ExpectedSeparator => "expected \",\" or \";\"".to_string(),
+            ParseErrorKind::UnknownColor(word) => format!("unknown color \"{}\"", word),
+            ParseErrorKind::DuplicateColor(color) => {
+                format!("color {:?} appears more than once in a set", color)
+            }
+            ParseErrorKind::AmbiguousToken(token) => format!("ambiguous token \"{}\"", token),
+            ParseErrorKind::NumberOverflow => "number is too large".to_string(),
+            ParseErrorKind::UnexpectedCharacter(c) => format!("unexpected character {:?}", c),
+        };
+        write!(f, "{} at {}..{}", message, self.span.start, self.span.end)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, PartialEq, Clone)]
+enum TokenKind {
+    Word(String),
+    Number(u32),
+    Colon,
+    Semicolon,
+    Comma,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    span: Range<usize>,
+}
+
+fn error<T>(kind: ParseErrorKind, span: Range<usize>) -> Result<T, ParseError> {
+    Err(ParseError { kind, span })
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, ParseError> {
+    let mut tokens: Vec<Token> = vec![];
+    let mut chars = input.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        let kind = match c {
+            ':' => Some(TokenKind::Colon),
+            ';' => Some(TokenKind::Semicolon),
+            ',' => Some(TokenKind::Comma),
+            _ => None,
+        };
+        if let Some(kind) = kind {
+            chars.next();
+            tokens.push(Token {
+                kind,
+                span: start..start + 1,
+            });
+            continue;
+        }
+
+        if !c.is_alphanumeric() {
+            return error(
+                ParseErrorKind::UnexpectedCharacter(c),
+                start..start + c.len_utf8(),
+            );
+        }
+
+        // consume a run of letters and digits, then decide what it was
+        let mut end = start;
+        while let Some(&(i, c)) = chars.peek() {
+            if !c.is_alphanumeric() {
+                break;
+            }
+            end = i + c.len_utf8();
+            chars.next();
+        }
+
+        let text = &input[start..end];
+        let kind = if text.chars().all(|c| c.is_ascii_digit()) {
+            match text.parse::<u32>() {
+                Ok(n) => TokenKind::Number(n),
+                Err(_) => return error(ParseErrorKind::NumberOverflow, start..end),
+            }
+        } else if text.chars().all(|c| c.is_alphabetic()) {
+            TokenKind::Word(text.to_lowercase())
+        } else {
+            return error(ParseErrorKind::AmbiguousToken(text.to_string()), start..end);
+        };
+
+        tokens.push(Token {
+            kind,
+            span: start..end,
+        });
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    // used to point errors at the end of the line
+    len: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn span_here(&self) -> Range<usize> {
+        match self.peek() {
+            Some(token) => token.span.clone(),
+            None => self.len..self.len,
+        }
+    }
+
+    fn expect_number(&mut self) -> Result<u32, ParseError> {
+        let span = self.span_here();
+        match self.next() {
+            Some(Token {
+                kind: TokenKind::Number(n),
+                ..
+            }) => Ok(n),
+            _ => error(ParseErrorKind::ExpectedNumber, span),
+        }
+    }
+
+    fn expect_color(&mut self) -> Result<(Color, Range<usize>), ParseError> {
+        let span = self.span_here();
+        match self.next() {
+            Some(Token {
+                kind: TokenKind::Word(word),
+                span,
+            }) => match word.as_str() {
+                "red" | "reds" => Ok((Color::Red, span)),
+                "green" | "greens" => Ok((Color::Green, span)),
+                "blue" | "blues" => Ok((Color::Blue, span)),
+                _ => error(ParseErrorKind::UnknownColor(word), span),
+            },
+            _ => error(ParseErrorKind::ExpectedColor, span),
+        }
+    }
+
+    fn parse_game(&mut self) -> Result<Game, ParseError> {
+        let span = self.span_here();
+        match self.next() {
+            Some(Token {
+                kind: TokenKind::Word(word),
+                ..
+            }) if word == "game" => {}
+            _ => return error(ParseErrorKind::ExpectedGameKeyword, span),
+        }
+
+        let id = self.expect_number()?;
+
+        let span = self.span_here();
+        match self.next() {
+            Some(Token {
+                kind: TokenKind::Colon,
+                ..
+            }) => {}
+            _ => return error(ParseErrorKind::ExpectedColon, span),
+        }
+
+        let mut sets: Vec<CubeSet> = vec![self.parse_set()?];
+        loop {
+            let span = self.span_here();
+            match self.next() {
+                None => break,
+                Some(Token {
+                    kind: TokenKind::Semicolon,
+                    ..
+                }) => sets.push(self.parse_set()?),
+                Some(_) => return error(ParseErrorKind::ExpectedSeparator, span),
+            }
+        }
+
+        Ok(Game { id, sets })
+    }
+
+    fn parse_set(&mut self) -> Result<CubeSet, ParseError> {
+        let mut set = CubeSet {
+            red: 0,
+            blue: 0,
+            green: 0,
+        };
+        let mut seen: Vec<Color> = vec![];
+
+        loop {
+            let count = self.expect_number()?;
+            let (color, span) = self.expect_color()?;
+
+            // "1 red, 2 red" has no single meaning, so it is rejected
+            if seen.contains(&color) {
+                return error(ParseErrorKind::DuplicateColor(color), span);
+            }
+            seen.push(color);
+
+            match color {
+                Color::Red => set.red = count,
+                Color::Green => set.green = count,
+                Color::Blue => set.blue = count,
+            }
+
+            match self.peek().map(|t| &t.kind) {
+                Some(TokenKind::Comma) => {
+                    self.next();
+                }
+                Some(TokenKind::Semicolon) | None => break,
+                Some(_) => return error(ParseErrorKind::ExpectedSeparator, self.span_here()),
+            }
+        }
+
+        Ok(set)
+    }
+}
+
+/// Parses a line like `Game 1: 3 blue, 4 red; 1 red, 2 green`.
+pub fn parse_game(input: &str) -> Result<Game, ParseError> {
+    let mut parser = Parser {
+        tokens: tokenize(input)?,
+        position: 0,
+        len: input.len(),
+    };
+    parser.parse_game()
+}
+
+/// Parses a single set like `3 blue, 4 red`, without the game prefix.
+pub fn parse_set(input: &str) -> Result<CubeSet, ParseError> {
+    let mut parser = Parser {
+        tokens: tokenize(input)?,
+        position: 0,
+        len: input.len(),
+    };
+    let set = parser.parse_set()?;
+
+    match parser.peek() {
+        None => Ok(set),
+        Some(token) => error(ParseErrorKind::ExpectedSeparator, token.span.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_game, parse_set, Color, CubeSet, ParseError, ParseErrorKind};
+
+    #[test]
+    fn it_parses_flexible_whitespace_and_casing() {
+        let input = "  GAME   7 :3 Blue,4 REDS ;  1 red ,2 greens  ";
+        let actual = parse_game(input).unwrap();
+
+        assert_eq!(actual.id, 7);
+        assert_eq!(
+            actual.sets,
+            vec![
+                CubeSet {
+                    red: 4,
+                    blue: 3,
+                    green: 0,
+                },
+                CubeSet {
+                    red: 1,
+                    blue: 0,
+                    green: 2,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn it_rejects_unknown_color() {
+        let input = "Game 1: 12 greenish";
+        let actual = parse_game(input);
+
+        let expected = Err(ParseError {
+            kind: ParseErrorKind::UnknownColor("greenish".to_string()),
+            span: 11..19,
+        });
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn it_rejects_missing_colon() {
+        let input = "Game 1 3 blue";
+        let actual = parse_game(input);
+
+        let expected = Err(ParseError {
+            kind: ParseErrorKind::ExpectedColon,
+            span: 7..8,
+        });
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn it_rejects_ambiguous_tokens() {
+        let input = "Game 1: 12green";
+        let actual = parse_game(input);
+
+        let expected = Err(ParseError {
+            kind: ParseErrorKind::AmbiguousToken("12green".to_string()),
+            span: 8..15,
+        });
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn it_rejects_duplicate_colors() {
+        let input = "1 red, 2 red";
+        let actual = parse_set(input);
+
+        let expected = Err(ParseError {
+            kind: ParseErrorKind::DuplicateColor(Color::Red),
+            span: 9..12,
+        });
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn it_rejects_empty_set() {
+        let input = "Game 1: 3 blue;";
+        let actual = parse_game(input);
+
+        let expected = Err(ParseError {
+            kind: ParseErrorKind::ExpectedNumber,
+            span: 15..15,
+        });
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn it_rejects_missing_separator() {
+        let input = "Game 1: 3 blue 4 red";
+        let actual = parse_game(input);
+
+        let expected = Err(ParseError {
+            kind: ParseErrorKind::ExpectedSeparator,
+            span: 15..16,
+        });
+
+        assert_eq!(actual, expected);
+    }
+}
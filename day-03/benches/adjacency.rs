@@ -0,0 +1,150 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::{Hash, Hasher},
+};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use day_03::schematic::{find_adjacent_cells, find_gear_ratio, parse_grid, Cell, Schematic};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CellType {
+    Blank,
+    Value,
+    Symbol,
+}
+
+// the previous cell, one copy per character with numbers told apart
+// by a "row,col,value" id string
+#[derive(Debug, Clone, Eq)]
+struct LegacyCell {
+    id: String,
+    val: String,
+    row: u32,
+    col: u32,
+    kind: CellType,
+}
+
+impl Hash for LegacyCell {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state)
+    }
+}
+
+impl PartialEq for LegacyCell {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+// the string-keyed HashMap grid this crate used before `Grid`, every
+// neighbour lookup formats and hashes a "row,col" key
+fn to_string_keyed(schematic: &Schematic) -> HashMap<String, LegacyCell> {
+    schematic
+        .grid()
+        .iter()
+        .map(|(row, col, cell)| {
+            let (id, val, kind) = match cell {
+                Cell::Blank => (format!("{row},{col}"), ".".to_string(), CellType::Blank),
+                Cell::Symbol(c) => (format!("{row},{col}"), c.to_string(), CellType::Symbol),
+                Cell::Part(_) => {
+                    let part = schematic.part_at(row, col).unwrap();
+                    let id = format!("{},{},{}", row, part.col_span.start, part.text);
+                    (id, part.text.clone(), CellType::Value)
+                }
+            };
+            let cell = LegacyCell {
+                id,
+                val,
+                row: row as u32,
+                col: col as u32,
+                kind,
+            };
+            (format!("{row},{col}"), cell)
+        })
+        .collect()
+}
+
+fn string_keyed_neighbors(
+    map: &HashMap<String, LegacyCell>,
+    row: i32,
+    col: i32,
+) -> Vec<Option<&LegacyCell>> {
+    vec![
+        map.get(&format!("{},{}", row - 1, col - 1)),
+        map.get(&format!("{},{}", row - 1, col)),
+        map.get(&format!("{},{}", row - 1, col + 1)),
+        map.get(&format!("{},{}", row, col + 1)),
+        map.get(&format!("{},{}", row + 1, col + 1)),
+        map.get(&format!("{},{}", row + 1, col)),
+        map.get(&format!("{},{}", row + 1, col - 1)),
+        map.get(&format!("{},{}", row, col - 1)),
+    ]
+}
+
+fn string_keyed_adjacent_cells(map: &HashMap<String, LegacyCell>) -> HashSet<&LegacyCell> {
+    let mut set: HashSet<&LegacyCell> = HashSet::new();
+
+    for v in map.values() {
+        if v.kind == CellType::Value {
+            let neighbors = string_keyed_neighbors(map, v.row as i32, v.col as i32);
+            if neighbors
+                .into_iter()
+                .flatten()
+                .any(|cell| cell.kind == CellType::Symbol)
+            {
+                set.insert(v);
+            }
+        }
+    }
+
+    set
+}
+
+fn string_keyed_gear_ratio(map: &HashMap<String, LegacyCell>) -> u64 {
+    let mut sum = 0;
+
+    for v in map.values() {
+        if v.kind == CellType::Symbol && v.val == "*" {
+            let list = string_keyed_neighbors(map, v.row as i32, v.col as i32)
+                .into_iter()
+                .flatten()
+                .filter(|cell| cell.kind == CellType::Value)
+                .collect::<HashSet<&LegacyCell>>()
+                .into_iter()
+                .collect::<Vec<&LegacyCell>>();
+
+            if list.len() == 2 {
+                sum += list[0].val.parse::<u64>().unwrap() * list[1].val.parse::<u64>().unwrap();
+            }
+        }
+    }
+
+    sum
+}
+
+fn bench_adjacency(c: &mut Criterion) {
+    let input = include_str!("../src/bin/input1.txt").trim();
+    let schematic = parse_grid(input);
+    let map = to_string_keyed(&schematic);
+
+    let mut group = c.benchmark_group("find_adjacent_cells");
+    group.bench_function("string_keyed", |b| {
+        b.iter(|| string_keyed_adjacent_cells(black_box(&map)).len())
+    });
+    group.bench_function("grid", |b| {
+        b.iter(|| find_adjacent_cells(black_box(&schematic)).len())
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("find_gear_ratio");
+    group.bench_function("string_keyed", |b| {
+        b.iter(|| string_keyed_gear_ratio(black_box(&map)))
+    });
+    group.bench_function("grid", |b| {
+        b.iter(|| find_gear_ratio(black_box(&schematic)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_adjacency);
+criterion_main!(benches);
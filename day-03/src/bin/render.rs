@@ -0,0 +1,21 @@
+use day_03::{
+    gears::default_rules,
+    render::{render_ansi, render_html},
+    schematic::parse_grid,
+};
+
+fn main() {
+    // cargo run --bin render > schematic.txt
+    // cargo run --bin render -- --html > schematic.html
+    let input = include_str!("input1.txt").trim();
+
+    let schematic = parse_grid(input);
+
+    let html = std::env::args().any(|arg| arg == "--html");
+
+    if html {
+        print!("{}", render_html(&schematic, &default_rules()));
+    } else {
+        print!("{}", render_ansi(&schematic, &default_rules()));
+    }
+}
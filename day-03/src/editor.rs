@@ -0,0 +1,360 @@
+use std::collections::HashSet;
+
+use crate::{
+    gears::{default_rules, find_gear_at, find_gears, GearRule},
+    grid::Neighborhood,
+    schematic::{
+        find_adjacent_cells, is_known, is_number_char, parse_grid_with, saturate, touches_symbol,
+        Diagnostic, ParseOptions, PartId, Schematic,
+    },
+};
+
+/// A parsed schematic that keeps the part sum and gear ratio up to date as
+/// cells are edited, rescanning only the area around each edit.
+///
+/// Adjacency is always the eight surrounding cells, since each edit only
+/// rescans one cell around the changed number.
+#[derive(Debug, Clone)]
+pub struct SchematicEditor {
+    schematic: Schematic,
+    options: ParseOptions,
+    rules: Vec<GearRule>,
+
+    // exact, so edits can take values back out; clamped only when read
+    part_sum: i128,
+    gear_ratio: i128,
+}
+
+// an inclusive rectangle of cells
+struct Region {
+    rows: (usize, usize),
+    cols: (usize, usize),
+}
+
+impl SchematicEditor {
+    pub fn new(
+        schematic: Schematic,
+        options: ParseOptions,
+        rules: Vec<GearRule>,
+    ) -> SchematicEditor {
+        let part_sum = find_adjacent_cells(&schematic)
+            .iter()
+            .map(|part| i128::from(part.value))
+            .sum();
+        let gear_ratio = find_gears(&schematic, &rules)
+            .iter()
+            .map(|gear| i128::from(gear.value))
+            .sum();
+
+        SchematicEditor {
+            schematic,
+            options,
+            rules,
+            part_sum,
+            gear_ratio,
+        }
+    }
+
+    pub fn parse(input: &str) -> SchematicEditor {
+        let options = ParseOptions::default();
+        let (schematic, _) = parse_grid_with(input, &options);
+        SchematicEditor::new(schematic, options, default_rules())
+    }
+
+    pub fn schematic(&self) -> &Schematic {
+        &self.schematic
+    }
+
+    /// Same as `find_signature` over `find_adjacent_cells`.
+    pub fn part_sum(&self) -> i64 {
+        saturate(self.part_sum)
+    }
+
+    /// Same as `find_gear_value` with this editor's rules.
+    pub fn gear_ratio(&self) -> i64 {
+        saturate(self.gear_ratio)
+    }
+
+    /// Replaces the character at `(row, col)` with a digit, `.` or a symbol.
+    ///
+    /// Panics if `(row, col)` is outside the grid.
+    pub fn set(&mut self, row: usize, col: usize, c: char) -> Result<(), Diagnostic> {
+        if !is_known(c, &self.options) {
+            return Err(Diagnostic::UnknownCharacter { row, col, found: c });
+        }
+        assert!(
+            self.schematic.grid().contains(row, col),
+            "({}, {}) is outside the grid",
+            row,
+            col
+        );
+
+        // every number that could be split, joined or changed sits in this row
+        // between `left` and `right`, bounded by characters no number can contain
+        let joins = |i: usize| is_number_char(self.schematic.glyph(row, i), &self.options);
+        let mut left = col;
+        while left > 0 && joins(left - 1) {
+            left -= 1;
+        }
+        let mut right = col;
+        while right + 1 < self.schematic.width() && joins(right + 1) {
+            right += 1;
+        }
+
+        // only numbers and symbols that can see the changed cells are affected
+        let region = Region {
+            rows: (
+                row.saturating_sub(1),
+                (row + 1).min(self.schematic.height() - 1),
+            ),
+            cols: (
+                left.saturating_sub(1),
+                (right + 1).min(self.schematic.width() - 1),
+            ),
+        };
+
+        let before_parts = self.region_part_sum(&region);
+        let before_gears = self.region_gear_ratio(&region);
+
+        let chars = (left..=right)
+            .map(|i| {
+                if i == col {
+                    c
+                } else {
+                    self.schematic.glyph(row, i)
+                }
+            })
+            .collect::<Vec<char>>();
+        self.schematic
+            .replace_segment(row, left, &chars, &self.options)?;
+
+        let after_parts = self.region_part_sum(&region);
+        let after_gears = self.region_gear_ratio(&region);
+
+        self.part_sum = self.part_sum - before_parts + after_parts;
+        self.gear_ratio = self.gear_ratio - before_gears + after_gears;
+
+        Ok(())
+    }
+
+    // the sum of the part numbers with at least one cell inside `region`
+    fn region_part_sum(&self, region: &Region) -> i128 {
+        let neighborhood = Neighborhood::moore();
+        let mut seen: HashSet<PartId> = HashSet::new();
+        let mut sum = 0;
+
+        for row in region.rows.0..=region.rows.1 {
+            for col in region.cols.0..=region.cols.1 {
+                let Some(part) = self.schematic.part_at(row, col) else {
+                    continue;
+                };
+                if !seen.insert(part.id) {
+                    continue;
+                }
+
+                if touches_symbol(&self.schematic, part, &neighborhood) {
+                    sum += i128::from(part.value);
+                }
+            }
+        }
+
+        sum
+    }
+
+    // the sum of the gears whose symbol is inside `region`
+    fn region_gear_ratio(&self, region: &Region) -> i128 {
+        let neighborhood = Neighborhood::moore();
+        let mut sum = 0;
+
+        for row in region.rows.0..=region.rows.1 {
+            for col in region.cols.0..=region.cols.1 {
+                if let Some(gear) =
+                    find_gear_at(&self.schematic, row, col, &self.rules, &neighborhood)
+                {
+                    sum += i128::from(gear.value);
+                }
+            }
+        }
+
+        sum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        editor::SchematicEditor,
+        gears::{default_rules, find_gear_value},
+        schematic::{
+            find_adjacent_cells, find_gear_ratio, find_signature, parse_grid_with, Diagnostic,
+            ParseOptions,
+        },
+    };
+
+    const EXAMPLE: &str = "
+    467..114..
+    ...*......
+    ..35..633.
+    ......#...
+    617*......
+    .....+.58.
+    ..592.....
+    ......755.
+    ...$.*....
+    .664.598..";
+
+    // recompute both answers from scratch to check the incremental ones
+    fn rescan(editor: &SchematicEditor) -> (i64, i64) {
+        let schematic = editor.schematic();
+        (
+            find_signature(find_adjacent_cells(schematic)),
+            find_gear_ratio(schematic),
+        )
+    }
+
+    #[test]
+    fn it_starts_with_the_full_answers() {
+        let editor = SchematicEditor::parse(EXAMPLE.trim());
+
+        assert_eq!(editor.part_sum(), 4361);
+        assert_eq!(editor.gear_ratio(), 467835);
+    }
+
+    #[test]
+    fn it_updates_when_symbols_change() {
+        let mut editor = SchematicEditor::parse(EXAMPLE.trim());
+
+        // 114 becomes a part, the '*' still only touches 467 and 35
+        editor.set(1, 4, '#').unwrap();
+        assert_eq!(editor.part_sum(), 4361 + 114);
+        assert_eq!(editor.gear_ratio(), 467835);
+
+        // removing the first gear drops 467 and its ratio
+        editor.set(1, 3, '.').unwrap();
+        assert_eq!(editor.part_sum(), 4361 + 114 - 467);
+        assert_eq!(editor.gear_ratio(), 467835 - 467 * 35);
+
+        assert_eq!((editor.part_sum(), editor.gear_ratio()), rescan(&editor));
+    }
+
+    #[test]
+    fn it_updates_when_numbers_split_and_join() {
+        let mut editor = SchematicEditor::parse(EXAMPLE.trim());
+
+        // 633 becomes 6 and 3, only 6 still touches the '#'
+        editor.set(2, 7, '.').unwrap();
+        assert_eq!(editor.schematic().glyph(2, 8), '3');
+        assert_eq!(editor.part_sum(), 4361 - 633 + 6);
+        assert_eq!((editor.part_sum(), editor.gear_ratio()), rescan(&editor));
+
+        // 617 and the '*' join into 6171
+        editor.set(4, 3, '1').unwrap();
+        assert_eq!((editor.part_sum(), editor.gear_ratio()), rescan(&editor));
+    }
+
+    #[test]
+    fn it_matches_a_rescan_after_many_edits() {
+        let mut editor = SchematicEditor::parse(EXAMPLE.trim());
+        let choices = ['.', '.', '.', '1', '5', '9', '*', '#', '*'];
+
+        // a small deterministic sequence, no rand dependency needed
+        let mut state: u64 = 42;
+        for _ in 0..500 {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let row = (state >> 33) as usize % 10;
+            let col = (state >> 17) as usize % 10;
+            let c = choices[(state >> 45) as usize % choices.len()];
+
+            editor.set(row, col, c).unwrap();
+            assert_eq!((editor.part_sum(), editor.gear_ratio()), rescan(&editor));
+        }
+    }
+
+    #[test]
+    fn it_rejects_unknown_characters() {
+        let mut editor = SchematicEditor::parse(EXAMPLE.trim());
+
+        let actual = editor.set(0, 0, ' ');
+        let expected = Err(Diagnostic::UnknownCharacter {
+            row: 0,
+            col: 0,
+            found: ' ',
+        });
+
+        assert_eq!(actual, expected);
+        assert_eq!(editor.part_sum(), 4361);
+    }
+
+    #[test]
+    fn it_rejects_numbers_out_of_range() {
+        // joining the two halves would overflow an i64, so nothing changes
+        let mut editor = SchematicEditor::parse("9999999999.9999999999*");
+
+        let actual = editor.set(0, 10, '9');
+        let expected = Err(Diagnostic::NumberOutOfRange {
+            row: 0,
+            col: 0,
+            text: "9".repeat(21),
+        });
+
+        assert_eq!(actual, expected);
+        assert_eq!(editor.part_sum(), 9999999999);
+        assert_eq!(editor.schematic().glyph(0, 10), '.');
+    }
+
+    #[test]
+    fn it_keeps_exact_totals_past_i64() {
+        // 9223372036854775807 + 1 does not fit, so the sum reads as i64::MAX
+        let mut editor = SchematicEditor::parse("9223372036854775807*1");
+        assert_eq!(editor.part_sum(), i64::MAX);
+
+        // taking the 1 back out lands on i64::MAX exactly, not one below it
+        editor.set(0, 20, '.').unwrap();
+        assert_eq!(editor.part_sum(), i64::MAX);
+        assert_eq!((editor.part_sum(), editor.gear_ratio()), rescan(&editor));
+
+        editor.set(0, 20, '2').unwrap();
+        assert_eq!((editor.part_sum(), editor.gear_ratio()), rescan(&editor));
+    }
+
+    #[test]
+    fn it_joins_signs_and_hex_prefixes() {
+        let options = ParseOptions {
+            signed_numbers: true,
+            hex_numbers: true,
+            ..Default::default()
+        };
+        let input = "
+        .-.12.0.1F
+        ....*.....
+        .3........"
+            .trim();
+        let (schematic, _) = parse_grid_with(input, &options);
+        let mut editor = SchematicEditor::new(schematic, options.clone(), default_rules());
+
+        let rescan = |editor: &SchematicEditor| {
+            let schematic = editor.schematic();
+            (
+                find_signature(find_adjacent_cells(schematic)),
+                find_gear_value(schematic, &default_rules()),
+            )
+        };
+
+        // until it joins 0x1F the 'F' is a symbol, so the 1 next to it is a part
+        assert_eq!(editor.part_sum(), 12 + 1);
+
+        // "-", the new "1" and "12" become -112
+        editor.set(0, 2, '1').unwrap();
+        assert_eq!(editor.schematic().glyph(0, 1), '-');
+        assert_eq!(editor.schematic().part_at(0, 1).unwrap().value, -112);
+        assert_eq!((editor.part_sum(), editor.gear_ratio()), rescan(&editor));
+
+        // "0", "x" and "1F" become 0x1F, which now touches the '*' as well
+        editor.set(0, 7, 'x').unwrap();
+        editor.set(1, 5, '*').unwrap();
+        assert_eq!(editor.schematic().part_at(0, 9).unwrap().value, 31);
+        assert_eq!((editor.part_sum(), editor.gear_ratio()), rescan(&editor));
+    }
+}
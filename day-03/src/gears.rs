@@ -0,0 +1,292 @@
+use crate::{
+    grid::Neighborhood,
+    schematic::{find_adjacent_parts_with, saturate, Cell, PartNumber, Schematic},
+};
+
+/// How many distinct part numbers a symbol needs around it to count as a gear.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NeighborCount {
+    pub min: Option<usize>,
+    pub max: Option<usize>,
+}
+
+impl NeighborCount {
+    pub fn exact(n: usize) -> NeighborCount {
+        NeighborCount {
+            min: Some(n),
+            max: Some(n),
+        }
+    }
+
+    pub fn at_least(n: usize) -> NeighborCount {
+        NeighborCount {
+            min: Some(n),
+            max: None,
+        }
+    }
+
+    pub fn at_most(n: usize) -> NeighborCount {
+        NeighborCount {
+            min: None,
+            max: Some(n),
+        }
+    }
+
+    pub fn allows(&self, n: usize) -> bool {
+        self.min.is_none_or(|min| n >= min) && self.max.is_none_or(|max| n <= max)
+    }
+}
+
+/// How the part numbers around a gear are combined into its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregation {
+    Product,
+    Sum,
+    Min,
+    Max,
+}
+
+impl Aggregation {
+    /// Combines `values`; a gear with no parts, or whose value does not fit
+    /// in an `i64`, has no value.
+    pub fn apply(&self, values: &[i64]) -> Option<i64> {
+        if values.is_empty() {
+            return None;
+        }
+
+        let mut iter = values.iter().copied();
+        match self {
+            Aggregation::Product => iter.try_fold(1i64, |acc, n| acc.checked_mul(n)),
+            Aggregation::Sum => iter.try_fold(0i64, |acc, n| acc.checked_add(n)),
+            Aggregation::Min => iter.min(),
+            Aggregation::Max => iter.max(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GearRule {
+    pub symbol: char,
+    pub count: NeighborCount,
+    pub aggregation: Aggregation,
+}
+
+impl GearRule {
+    pub fn new(symbol: char, count: NeighborCount, aggregation: Aggregation) -> GearRule {
+        GearRule {
+            symbol,
+            count,
+            aggregation,
+        }
+    }
+}
+
+/// The puzzle's rule, a `*` next to exactly two part numbers multiplies them.
+pub fn default_rules() -> Vec<GearRule> {
+    vec![GearRule::new(
+        '*',
+        NeighborCount::exact(2),
+        Aggregation::Product,
+    )]
+}
+
+#[derive(Debug)]
+pub struct Gear<'a> {
+    pub row: usize,
+    pub col: usize,
+    pub symbol: char,
+    pub parts: Vec<&'a PartNumber>,
+    pub value: i64,
+}
+
+pub fn find_gears<'a>(schematic: &'a Schematic, rules: &[GearRule]) -> Vec<Gear<'a>> {
+    find_gears_with(schematic, rules, &Neighborhood::default())
+}
+
+/// Every symbol that satisfies its rule, counting the parts in `neighborhood`;
+/// the first rule for a symbol wins.
+pub fn find_gears_with<'a>(
+    schematic: &'a Schematic,
+    rules: &[GearRule],
+    neighborhood: &Neighborhood,
+) -> Vec<Gear<'a>> {
+    schematic
+        .grid()
+        .iter()
+        .filter_map(|(row, col, _)| find_gear_at(schematic, row, col, rules, neighborhood))
+        .collect()
+}
+
+/// The gear at `(row, col)`, if that cell is a symbol that satisfies its rule.
+pub fn find_gear_at<'a>(
+    schematic: &'a Schematic,
+    row: usize,
+    col: usize,
+    rules: &[GearRule],
+    neighborhood: &Neighborhood,
+) -> Option<Gear<'a>> {
+    let Cell::Symbol(symbol) = schematic.grid()[(row, col)] else {
+        return None;
+    };
+
+    let rule = rules.iter().find(|rule| rule.symbol == symbol)?;
+
+    let parts = find_adjacent_parts_with(schematic, row, col, neighborhood);
+    if !rule.count.allows(parts.len()) {
+        return None;
+    }
+
+    let values = parts.iter().map(|part| part.value).collect::<Vec<i64>>();
+
+    let value = rule.aggregation.apply(&values)?;
+
+    Some(Gear {
+        row,
+        col,
+        symbol,
+        parts,
+        value,
+    })
+}
+
+pub fn find_gear_value(schematic: &Schematic, rules: &[GearRule]) -> i64 {
+    find_gear_value_with(schematic, rules, &Neighborhood::default())
+}
+
+/// The sum of every gear's value, clamped to `i64::MIN..=i64::MAX` if it
+/// does not fit.
+pub fn find_gear_value_with(
+    schematic: &Schematic,
+    rules: &[GearRule],
+    neighborhood: &Neighborhood,
+) -> i64 {
+    saturate(
+        find_gears_with(schematic, rules, neighborhood)
+            .iter()
+            .map(|gear| i128::from(gear.value))
+            .sum(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        gears::{
+            default_rules, find_gear_value, find_gear_value_with, find_gears, Aggregation,
+            GearRule, NeighborCount,
+        },
+        grid::{Metric, Neighborhood},
+        schematic::parse_grid,
+    };
+
+    const EXAMPLE: &str = "
+    467..114..
+    ...*......
+    ..35..633.
+    ......#...
+    617*......
+    .....+.58.
+    ..592.....
+    ......755.
+    ...$.*....
+    .664.598..";
+
+    #[test]
+    fn it_matches_the_puzzle_rule() {
+        let schematic = parse_grid(EXAMPLE.trim());
+
+        assert_eq!(find_gear_value(&schematic, &default_rules()), 467835);
+    }
+
+    #[test]
+    fn it_sums_all_adjacent_parts() {
+        let schematic = parse_grid(EXAMPLE.trim());
+
+        let rules = vec![GearRule::new(
+            '#',
+            NeighborCount::at_least(1),
+            Aggregation::Sum,
+        )];
+
+        let gears = find_gears(&schematic, &rules);
+
+        assert_eq!(gears.len(), 1);
+        assert_eq!((gears[0].row, gears[0].col), (3, 6));
+        assert_eq!(gears[0].value, 633);
+    }
+
+    #[test]
+    fn it_requires_the_declared_count() {
+        let input = "
+        .12.34.
+        ...*...
+        ..56..."
+            .trim();
+        let schematic = parse_grid(input);
+
+        let pairs = vec![GearRule::new(
+            '*',
+            NeighborCount::exact(2),
+            Aggregation::Product,
+        )];
+        let triples = vec![GearRule::new(
+            '*',
+            NeighborCount::exact(3),
+            Aggregation::Product,
+        )];
+        let at_most_two = vec![GearRule::new(
+            '*',
+            NeighborCount::at_most(2),
+            Aggregation::Max,
+        )];
+
+        assert_eq!(find_gear_value(&schematic, &pairs), 0);
+        assert_eq!(find_gear_value(&schematic, &triples), 12 * 34 * 56);
+        assert_eq!(find_gear_value(&schematic, &at_most_two), 0);
+    }
+
+    #[test]
+    fn it_aggregates() {
+        let values = vec![3, 5, 2];
+
+        assert_eq!(Aggregation::Product.apply(&values), Some(30));
+        assert_eq!(Aggregation::Sum.apply(&values), Some(10));
+        assert_eq!(Aggregation::Min.apply(&values), Some(2));
+        assert_eq!(Aggregation::Max.apply(&values), Some(5));
+        assert_eq!(Aggregation::Sum.apply(&[]), None);
+    }
+
+    #[test]
+    fn it_drops_gears_that_overflow() {
+        assert_eq!(Aggregation::Product.apply(&[i64::MAX, 2]), None);
+        assert_eq!(Aggregation::Sum.apply(&[i64::MAX, 1]), None);
+        assert_eq!(Aggregation::Sum.apply(&[i64::MAX, -1, 1]), Some(i64::MAX));
+
+        let schematic = parse_grid("4294967296*4294967296");
+        assert_eq!(find_gear_value(&schematic, &default_rules()), 0);
+    }
+
+    #[test]
+    fn it_uses_the_selected_neighborhood() {
+        let input = "
+        3.....
+        .*.5..
+        ..4..."
+            .trim();
+        let schematic = parse_grid(input);
+
+        let rules = vec![GearRule::new(
+            '*',
+            NeighborCount::at_least(1),
+            Aggregation::Sum,
+        )];
+
+        // 3 and 4 are diagonal, 5 is two columns away
+        let cardinal = Neighborhood::von_neumann();
+        let wide = Neighborhood::new(Metric::Chebyshev, 2);
+
+        assert_eq!(find_gear_value(&schematic, &rules), 7);
+        assert_eq!(find_gear_value_with(&schematic, &rules, &cardinal), 0);
+        assert_eq!(find_gear_value_with(&schematic, &rules, &wide), 12);
+    }
+}
@@ -0,0 +1,278 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use serde::Serialize;
+
+use crate::schematic::{Cell, PartId, PartNumber, Schematic};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SymbolNode {
+    pub id: usize,
+    pub symbol: char,
+    pub row: usize,
+    pub col: usize,
+}
+
+/// The bipartite graph of part numbers and the symbols they touch.
+#[derive(Debug, Clone)]
+pub struct SchematicGraph {
+    // ordered by id
+    pub parts: Vec<PartNumber>,
+    pub symbols: Vec<SymbolNode>,
+
+    // adjacency lists in both directions
+    part_to_symbols: HashMap<PartId, Vec<usize>>,
+    symbol_to_parts: Vec<Vec<PartId>>,
+}
+
+#[derive(Serialize)]
+struct GraphJson<'a> {
+    parts: &'a [PartNumber],
+    symbols: &'a [SymbolNode],
+    // (part id, symbol id)
+    edges: Vec<(PartId, usize)>,
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl SchematicGraph {
+    pub fn from_schematic(schematic: &Schematic) -> SchematicGraph {
+        let parts = schematic.parts().cloned().collect::<Vec<PartNumber>>();
+        let mut symbols: Vec<SymbolNode> = vec![];
+
+        for (row, col, cell) in schematic.grid().iter() {
+            if let Cell::Symbol(symbol) = cell {
+                symbols.push(SymbolNode {
+                    id: symbols.len(),
+                    symbol: *symbol,
+                    row,
+                    col,
+                });
+            }
+        }
+
+        let mut part_to_symbols: HashMap<PartId, Vec<usize>> =
+            parts.iter().map(|part| (part.id, vec![])).collect();
+        let mut symbol_to_parts: Vec<Vec<PartId>> = vec![vec![]; symbols.len()];
+
+        for symbol in symbols.iter() {
+            for (_, _, cell) in schematic.grid().neighbors(symbol.row, symbol.col) {
+                if let Cell::Part(part) = cell {
+                    if !symbol_to_parts[symbol.id].contains(part) {
+                        symbol_to_parts[symbol.id].push(*part);
+                        part_to_symbols.get_mut(part).unwrap().push(symbol.id);
+                    }
+                }
+            }
+        }
+
+        for list in part_to_symbols.values_mut() {
+            list.sort();
+        }
+        for list in symbol_to_parts.iter_mut() {
+            list.sort();
+        }
+
+        SchematicGraph {
+            parts,
+            symbols,
+            part_to_symbols,
+            symbol_to_parts,
+        }
+    }
+
+    pub fn part(&self, id: PartId) -> Option<&PartNumber> {
+        self.parts
+            .binary_search_by_key(&id, |p| p.id)
+            .ok()
+            .map(|i| &self.parts[i])
+    }
+
+    /// The part covering `(row, col)`, if any.
+    pub fn part_at(&self, row: usize, col: usize) -> Option<&PartNumber> {
+        self.parts
+            .iter()
+            .find(|p| p.row == row && p.col_span.contains(&col))
+    }
+
+    pub fn symbols_for_part(&self, part: PartId) -> Vec<&SymbolNode> {
+        self.part_to_symbols
+            .get(&part)
+            .map(|ids| ids.iter().map(|id| &self.symbols[*id]).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn parts_for_symbol(&self, symbol: usize) -> Vec<&PartNumber> {
+        self.symbol_to_parts
+            .get(symbol)
+            .map(|ids| ids.iter().filter_map(|id| self.part(*id)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Groups of part ids linked through shared symbols; a part touching
+    /// no symbol is a group of its own.
+    pub fn connected_components(&self) -> Vec<Vec<PartId>> {
+        let mut seen: HashSet<PartId> = HashSet::new();
+        let mut components: Vec<Vec<PartId>> = vec![];
+
+        for start in self.parts.iter().map(|p| p.id) {
+            if seen.contains(&start) {
+                continue;
+            }
+
+            let mut component: Vec<PartId> = vec![];
+            let mut queue: VecDeque<PartId> = VecDeque::from([start]);
+            seen.insert(start);
+
+            while let Some(part) = queue.pop_front() {
+                component.push(part);
+                for symbol in self.part_to_symbols[&part].iter() {
+                    for next in self.symbol_to_parts[*symbol].iter() {
+                        if seen.insert(*next) {
+                            queue.push_back(*next);
+                        }
+                    }
+                }
+            }
+
+            component.sort();
+            components.push(component);
+        }
+
+        components
+    }
+
+    fn edges(&self) -> Vec<(PartId, usize)> {
+        self.parts
+            .iter()
+            .flat_map(|part| {
+                self.part_to_symbols[&part.id]
+                    .iter()
+                    .map(move |symbol| (part.id, *symbol))
+            })
+            .collect()
+    }
+
+    /// Graphviz output, parts are boxes and symbols are circles.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("graph schematic {\n");
+
+        for part in self.parts.iter() {
+            out.push_str(&format!(
+                "    p{} [shape=box, label=\"{} ({},{}..{})\"];\n",
+                part.id.0, part.value, part.row, part.col_span.start, part.col_span.end
+            ));
+        }
+
+        for symbol in self.symbols.iter() {
+            out.push_str(&format!(
+                "    s{} [shape=circle, label=\"{} ({},{})\"];\n",
+                symbol.id,
+                escape(&symbol.symbol.to_string()),
+                symbol.row,
+                symbol.col
+            ));
+        }
+
+        for (part, symbol) in self.edges() {
+            out.push_str(&format!("    p{} -- s{};\n", part.0, symbol));
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
+    pub fn to_json(&self) -> String {
+        let json = GraphJson {
+            parts: &self.parts,
+            symbols: &self.symbols,
+            edges: self.edges(),
+        };
+
+        serde_json::to_string_pretty(&json).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        graph::SchematicGraph,
+        schematic::{parse_grid, PartId},
+    };
+
+    #[test]
+    fn it_builds_the_graph() {
+        let input = "
+        467..114..
+        ...*......
+        ..35..633.
+        ......#..."
+            .trim();
+
+        let graph = SchematicGraph::from_schematic(&parse_grid(input));
+
+        let values = graph.parts.iter().map(|p| p.value).collect::<Vec<i64>>();
+        assert_eq!(values, vec![467, 114, 35, 633]);
+
+        let part = graph.part_at(2, 7).unwrap();
+        assert_eq!((part.value, part.col_span.clone()), (633, 6..9));
+
+        let symbols = graph.symbols_for_part(part.id);
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(symbols[0].symbol, '#');
+
+        let star = graph.symbols.iter().find(|s| s.symbol == '*').unwrap();
+        let parts = graph
+            .parts_for_symbol(star.id)
+            .iter()
+            .map(|p| p.value)
+            .collect::<Vec<i64>>();
+        assert_eq!(parts, vec![467, 35]);
+
+        assert!(graph.symbols_for_part(PartId(1)).is_empty());
+    }
+
+    #[test]
+    fn it_finds_connected_components() {
+        // 1 and 2 share the '*', 2 and 3 share the '#', 4 touches nothing
+        let input = "
+        1.....4
+        .*.....
+        2#3...."
+            .trim();
+
+        let graph = SchematicGraph::from_schematic(&parse_grid(input));
+
+        let components = graph
+            .connected_components()
+            .into_iter()
+            .map(|c| c.iter().map(|id| graph.part(*id).unwrap().value).collect())
+            .collect::<Vec<Vec<i64>>>();
+
+        assert_eq!(components, vec![vec![1, 2, 3], vec![4]]);
+    }
+
+    #[test]
+    fn it_exports_dot_and_json() {
+        let input = "
+        12.
+        .*."
+        .trim();
+
+        let graph = SchematicGraph::from_schematic(&parse_grid(input));
+
+        let expected_dot = "graph schematic {
+    p0 [shape=box, label=\"12 (0,0..2)\"];
+    s0 [shape=circle, label=\"* (1,1)\"];
+    p0 -- s0;
+}
+";
+        assert_eq!(graph.to_dot(), expected_dot);
+
+        let json: serde_json::Value = serde_json::from_str(&graph.to_json()).unwrap();
+        assert_eq!(json["parts"][0]["value"], 12);
+        assert_eq!(json["symbols"][0]["symbol"], "*");
+        assert_eq!(json["edges"][0], serde_json::json!([0, 0]));
+    }
+}
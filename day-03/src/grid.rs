@@ -0,0 +1,323 @@
+use std::{
+    ops::{Index, IndexMut},
+    sync::LazyLock,
+};
+
+/// How distance is measured when building a neighborhood.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    // squares, diagonals count as one step
+    Chebyshev,
+    // diamonds, diagonals count as two steps
+    Manhattan,
+}
+
+/// The set of offsets that count as "adjacent" to a cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Neighborhood {
+    metric: Metric,
+    radius: usize,
+    wrap: bool,
+    offsets: Vec<(isize, isize)>,
+}
+
+impl Neighborhood {
+    pub fn new(metric: Metric, radius: usize) -> Neighborhood {
+        let r = radius as isize;
+        let mut offsets: Vec<(isize, isize)> = vec![];
+
+        for dr in -r..=r {
+            for dc in -r..=r {
+                let distance = match metric {
+                    Metric::Chebyshev => dr.abs().max(dc.abs()),
+                    Metric::Manhattan => dr.abs() + dc.abs(),
+                };
+                if distance != 0 && distance <= r {
+                    offsets.push((dr, dc));
+                }
+            }
+        }
+
+        Neighborhood {
+            metric,
+            radius,
+            wrap: false,
+            offsets,
+        }
+    }
+
+    /// The eight surrounding cells.
+    pub fn moore() -> Neighborhood {
+        Neighborhood::new(Metric::Chebyshev, 1)
+    }
+
+    /// The four cells sharing an edge.
+    pub fn von_neumann() -> Neighborhood {
+        Neighborhood::new(Metric::Manhattan, 1)
+    }
+
+    /// Edges wrap around, so the grid behaves like a torus.
+    pub fn wrapping(mut self) -> Neighborhood {
+        self.wrap = true;
+        self
+    }
+
+    pub fn metric(&self) -> Metric {
+        self.metric
+    }
+
+    pub fn radius(&self) -> usize {
+        self.radius
+    }
+
+    pub fn wraps(&self) -> bool {
+        self.wrap
+    }
+
+    pub fn offsets(&self) -> &[(isize, isize)] {
+        &self.offsets
+    }
+}
+
+impl Default for Neighborhood {
+    fn default() -> Neighborhood {
+        Neighborhood::moore()
+    }
+}
+
+static MOORE: LazyLock<Neighborhood> = LazyLock::new(Neighborhood::moore);
+
+/// A dense, row-major 2D grid addressed by `(row, col)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T> {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from rows of equal length; returns `None` for ragged rows.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Grid<T>> {
+        let height = rows.len();
+        let width = rows.first().map(|r| r.len()).unwrap_or(0);
+
+        if rows.iter().any(|r| r.len() != width) {
+            return None;
+        }
+
+        let cells = rows.into_iter().flatten().collect::<Vec<T>>();
+
+        Some(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        if self.contains(row, col) {
+            Some(&self.cells[row * self.width + col])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        if self.contains(row, col) {
+            Some(&mut self.cells[row * self.width + col])
+        } else {
+            None
+        }
+    }
+
+    /// The cells of a single row, left to right.
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        if row < self.height {
+            let start = row * self.width;
+            Some(&self.cells[start..start + self.width])
+        } else {
+            None
+        }
+    }
+
+    /// The cells of a single column, top to bottom.
+    pub fn column(&self, col: usize) -> Option<impl Iterator<Item = &T>> {
+        if col < self.width {
+            Some(self.cells.iter().skip(col).step_by(self.width))
+        } else {
+            None
+        }
+    }
+
+    /// Every cell with its coordinates, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| (i / width, i % width, cell))
+    }
+
+    /// The up to eight cells surrounding `(row, col)`; cells past an edge are skipped.
+    pub fn neighbors(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize, &T)> {
+        self.neighbors_in(row, col, &MOORE)
+    }
+
+    /// The cells around `(row, col)` for any neighborhood. Without wrapping,
+    /// cells past an edge are skipped; with wrapping on a grid smaller than the
+    /// neighborhood the same cell can be reached more than once.
+    pub fn neighbors_in<'a, 'n>(
+        &'a self,
+        row: usize,
+        col: usize,
+        neighborhood: &'n Neighborhood,
+    ) -> impl Iterator<Item = (usize, usize, &'a T)> + use<'a, 'n, T> {
+        neighborhood.offsets.iter().filter_map(move |(dr, dc)| {
+            let (r, c) = if neighborhood.wrap {
+                if self.width == 0 || self.height == 0 {
+                    return None;
+                }
+                let r = (row as isize + dr).rem_euclid(self.height as isize) as usize;
+                let c = (col as isize + dc).rem_euclid(self.width as isize) as usize;
+                if (r, c) == (row, col) {
+                    return None;
+                }
+                (r, c)
+            } else {
+                (row.checked_add_signed(*dr)?, col.checked_add_signed(*dc)?)
+            };
+            self.get(r, c).map(|cell| (r, c, cell))
+        })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        self.get(row, col)
+            .unwrap_or_else(|| panic!("({}, {}) is outside the grid", row, col))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        self.get_mut(row, col)
+            .unwrap_or_else(|| panic!("({}, {}) is outside the grid", row, col))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::{Grid, Metric, Neighborhood};
+
+    fn sample() -> Grid<u32> {
+        // 0 1 2
+        // 3 4 5
+        Grid::from_rows(vec![vec![0, 1, 2], vec![3, 4, 5]]).unwrap()
+    }
+
+    #[test]
+    fn it_indexes() {
+        let grid = sample();
+
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[(1, 2)], 5);
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.get(0, 3), None);
+    }
+
+    #[test]
+    fn it_rejects_ragged_rows() {
+        let grid = Grid::from_rows(vec![vec![0, 1], vec![2]]);
+
+        assert_eq!(grid, None);
+    }
+
+    #[test]
+    fn it_views_rows_and_columns() {
+        let grid = sample();
+
+        assert_eq!(grid.row(1), Some(&[3, 4, 5][..]));
+        assert_eq!(
+            grid.column(1).unwrap().copied().collect::<Vec<u32>>(),
+            vec![1, 4]
+        );
+        assert!(grid.row(2).is_none());
+        assert!(grid.column(3).is_none());
+    }
+
+    #[test]
+    fn it_finds_neighbors_within_bounds() {
+        let grid = sample();
+
+        let mut corner = grid
+            .neighbors(0, 0)
+            .map(|(_, _, v)| *v)
+            .collect::<Vec<u32>>();
+        corner.sort();
+
+        assert_eq!(corner, vec![1, 3, 4]);
+
+        let mut middle = grid
+            .neighbors(1, 1)
+            .map(|(_, _, v)| *v)
+            .collect::<Vec<u32>>();
+        middle.sort();
+
+        assert_eq!(middle, vec![0, 1, 2, 3, 5]);
+    }
+
+    #[test]
+    fn it_builds_neighborhoods() {
+        assert_eq!(Neighborhood::moore().offsets().len(), 8);
+        assert_eq!(Neighborhood::von_neumann().offsets().len(), 4);
+        assert_eq!(Neighborhood::new(Metric::Chebyshev, 2).offsets().len(), 24);
+        assert_eq!(Neighborhood::new(Metric::Manhattan, 2).offsets().len(), 12);
+    }
+
+    #[test]
+    fn it_finds_neighbors_in_a_neighborhood() {
+        let grid = sample();
+
+        let mut cardinal = grid
+            .neighbors_in(1, 1, &Neighborhood::von_neumann())
+            .map(|(_, _, v)| *v)
+            .collect::<Vec<u32>>();
+        cardinal.sort();
+
+        assert_eq!(cardinal, vec![1, 3, 5]);
+
+        // the left edge wraps to the right edge and the top to the bottom
+        let mut wrapped = grid
+            .neighbors_in(0, 0, &Neighborhood::von_neumann().wrapping())
+            .map(|(_, _, v)| *v)
+            .collect::<Vec<u32>>();
+        wrapped.sort();
+
+        assert_eq!(wrapped, vec![1, 2, 3, 3]);
+    }
+}
@@ -0,0 +1,6 @@
+pub mod editor;
+pub mod gears;
+pub mod graph;
+pub mod grid;
+pub mod render;
+pub mod schematic;
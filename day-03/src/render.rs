@@ -0,0 +1,231 @@
+use std::collections::HashSet;
+
+use crate::{
+    gears::{find_gears, Gear, GearRule},
+    grid::Grid,
+    schematic::{find_adjacent_cells, Cell, PartId, Schematic},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Highlight {
+    Blank,
+    // a number touching a symbol
+    Part,
+    // a number touching nothing
+    NonPart,
+    Symbol,
+    Gear,
+}
+
+const RESET: &str = "\x1b[0m";
+
+impl Highlight {
+    fn ansi(&self) -> Option<&'static str> {
+        match self {
+            Highlight::Blank => None,
+            Highlight::Part => Some("\x1b[1;32m"),
+            Highlight::NonPart => Some("\x1b[2m"),
+            Highlight::Symbol => Some("\x1b[36m"),
+            Highlight::Gear => Some("\x1b[1;33m"),
+        }
+    }
+
+    fn class(&self) -> &'static str {
+        match self {
+            Highlight::Blank => "blank",
+            Highlight::Part => "part",
+            Highlight::NonPart => "non-part",
+            Highlight::Symbol => "symbol",
+            Highlight::Gear => "gear",
+        }
+    }
+}
+
+/// How every cell should be drawn, from the same adjacency used for the answers.
+pub fn find_highlights(schematic: &Schematic, gears: &[Gear]) -> Grid<Highlight> {
+    let adjacent = find_adjacent_cells(schematic)
+        .into_iter()
+        .map(|part| part.id)
+        .collect::<HashSet<PartId>>();
+
+    let mut highlights = Grid::new(schematic.width(), schematic.height(), Highlight::Blank);
+
+    for (row, col, cell) in schematic.grid().iter() {
+        highlights[(row, col)] = match cell {
+            Cell::Blank => Highlight::Blank,
+            Cell::Part(id) if adjacent.contains(id) => Highlight::Part,
+            Cell::Part(_) => Highlight::NonPart,
+            Cell::Symbol(_) => Highlight::Symbol,
+        };
+    }
+
+    for gear in gears.iter() {
+        highlights[(gear.row, gear.col)] = Highlight::Gear;
+    }
+
+    highlights
+}
+
+fn annotations(gears: &[Gear], row: usize) -> Vec<String> {
+    gears
+        .iter()
+        .filter(|gear| gear.row == row)
+        .map(|gear| format!("{} at {} = {}", gear.symbol, gear.col, gear.value))
+        .collect()
+}
+
+/// The schematic with ANSI colors, each row followed by its gear ratios.
+pub fn render_ansi(schematic: &Schematic, rules: &[GearRule]) -> String {
+    let gears = find_gears(schematic, rules);
+    let highlights = find_highlights(schematic, &gears);
+
+    let mut out = String::new();
+
+    for row in 0..schematic.height() {
+        for col in 0..schematic.width() {
+            let c = schematic.glyph(row, col);
+
+            match highlights[(row, col)].ansi() {
+                Some(color) => out.push_str(&format!("{}{}{}", color, c, RESET)),
+                None => out.push(c),
+            }
+        }
+
+        let notes = annotations(&gears, row);
+        if !notes.is_empty() {
+            out.push_str(&format!("  \x1b[33m{}{}", notes.join(", "), RESET));
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+fn escape_html(c: char) -> String {
+    match c {
+        '&' => "&amp;".to_string(),
+        '<' => "&lt;".to_string(),
+        '>' => "&gt;".to_string(),
+        '"' => "&quot;".to_string(),
+        _ => c.to_string(),
+    }
+}
+
+/// A standalone HTML page of the schematic, gears carry their ratio as a tooltip.
+pub fn render_html(schematic: &Schematic, rules: &[GearRule]) -> String {
+    let gears = find_gears(schematic, rules);
+    let highlights = find_highlights(schematic, &gears);
+
+    let mut out = String::from(
+        "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>schematic</title>
+<style>
+body { background: #10101a; color: #cccccc; }
+.blank { color: #444444; }
+.part { color: #00cc00; font-weight: bold; }
+.non-part { color: #666666; }
+.symbol { color: #00cccc; }
+.gear { color: #ffff66; font-weight: bold; }
+.note { color: #cccc00; }
+</style>
+</head>
+<body>
+<pre>
+",
+    );
+
+    for row in 0..schematic.height() {
+        for col in 0..schematic.width() {
+            let c = schematic.glyph(row, col);
+
+            let highlight = highlights[(row, col)];
+            let title = gears
+                .iter()
+                .find(|gear| highlight == Highlight::Gear && gear.row == row && gear.col == col)
+                .map(|gear| format!(" title=\"{}\"", gear.value))
+                .unwrap_or_default();
+
+            out.push_str(&format!(
+                "<span class=\"{}\"{}>{}</span>",
+                highlight.class(),
+                title,
+                escape_html(c)
+            ));
+        }
+
+        let notes = annotations(&gears, row);
+        if !notes.is_empty() {
+            let text = notes
+                .join(", ")
+                .chars()
+                .map(escape_html)
+                .collect::<String>();
+            out.push_str(&format!("  <span class=\"note\">{}</span>", text));
+        }
+        out.push('\n');
+    }
+
+    out.push_str("</pre>\n</body>\n</html>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        gears::{default_rules, find_gears},
+        render::{find_highlights, render_ansi, render_html, Highlight},
+        schematic::parse_grid,
+    };
+
+    const INPUT: &str = "
+    12.7.
+    .*...
+    34...";
+
+    #[test]
+    fn it_highlights_parts_and_gears() {
+        let schematic = parse_grid(INPUT.trim());
+        let gears = find_gears(&schematic, &default_rules());
+
+        let highlights = find_highlights(&schematic, &gears);
+
+        assert_eq!(highlights[(0, 0)], Highlight::Part);
+        assert_eq!(highlights[(0, 1)], Highlight::Part);
+        assert_eq!(highlights[(0, 3)], Highlight::NonPart);
+        assert_eq!(highlights[(1, 1)], Highlight::Gear);
+        assert_eq!(highlights[(1, 0)], Highlight::Blank);
+    }
+
+    #[test]
+    fn it_renders_ansi() {
+        let schematic = parse_grid(INPUT.trim());
+
+        let out = render_ansi(&schematic, &default_rules());
+        let lines = out.lines().collect::<Vec<&str>>();
+
+        assert_eq!(
+            lines[0],
+            "\x1b[1;32m1\x1b[0m\x1b[1;32m2\x1b[0m.\x1b[2m7\x1b[0m."
+        );
+        assert_eq!(
+            lines[1],
+            ".\x1b[1;33m*\x1b[0m...  \x1b[33m* at 1 = 408\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn it_renders_html() {
+        let schematic = parse_grid(INPUT.trim());
+
+        let out = render_html(&schematic, &default_rules());
+
+        assert!(out.starts_with("<!DOCTYPE html>"));
+        assert!(out.contains("<span class=\"part\">1</span><span class=\"part\">2</span>"));
+        assert!(out.contains("<span class=\"non-part\">7</span>"));
+        assert!(out.contains("<span class=\"gear\" title=\"408\">*</span>"));
+        assert!(out.contains("<span class=\"note\">* at 1 = 408</span>"));
+    }
+}
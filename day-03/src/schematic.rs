@@ -0,0 +1,657 @@
+use std::{fmt, ops::Range};
+
+use serde::Serialize;
+
+use crate::{
+    gears::{default_rules, find_gear_value},
+    grid::{Grid, Neighborhood},
+};
+
+/// Identifies a part number within its schematic; ids are never reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub struct PartId(pub usize);
+
+/// A whole number on the schematic, covering `col_span` of its row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct PartNumber {
+    pub id: PartId,
+    pub value: i64,
+    pub row: usize,
+    pub col_span: Range<usize>,
+    // as written, so "0x1F" still renders as "0x1F"
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Cell {
+    Blank,
+    Symbol(char),
+    // every cell of a number points at the same part
+    Part(PartId),
+}
+
+/// The parsed grid plus the part numbers its cells refer to.
+#[derive(Debug, Clone)]
+pub struct Schematic {
+    grid: Grid<Cell>,
+    // indexed by `PartId`, removed parts leave a `None` behind
+    parts: Vec<Option<PartNumber>>,
+}
+
+impl Schematic {
+    pub fn grid(&self) -> &Grid<Cell> {
+        &self.grid
+    }
+
+    pub fn width(&self) -> usize {
+        self.grid.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.grid.height()
+    }
+
+    /// All part numbers, ordered by id.
+    pub fn parts(&self) -> impl Iterator<Item = &PartNumber> {
+        self.parts.iter().flatten()
+    }
+
+    pub fn part(&self, id: PartId) -> Option<&PartNumber> {
+        self.parts.get(id.0).and_then(|p| p.as_ref())
+    }
+
+    /// The part covering `(row, col)`, if any.
+    pub fn part_at(&self, row: usize, col: usize) -> Option<&PartNumber> {
+        match self.grid.get(row, col) {
+            Some(Cell::Part(id)) => self.part(*id),
+            _ => None,
+        }
+    }
+
+    /// The character drawn at `(row, col)`.
+    pub fn glyph(&self, row: usize, col: usize) -> char {
+        match self.grid[(row, col)] {
+            Cell::Blank => '.',
+            Cell::Symbol(c) => c,
+            Cell::Part(id) => {
+                let part = self.part(id).unwrap();
+                part.text.chars().nth(col - part.col_span.start).unwrap()
+            }
+        }
+    }
+
+    /// Re-reads `chars` into columns `left..` of `row`, replacing any parts there.
+    /// The slice must cover whole numbers, so the columns on either side of it
+    /// must not be able to join a number.
+    pub(crate) fn replace_segment(
+        &mut self,
+        row: usize,
+        left: usize,
+        chars: &[char],
+        options: &ParseOptions,
+    ) -> Result<(), Diagnostic> {
+        // check the new numbers before anything is touched
+        let (tokens, diagnostics) = tokenize_row(row, left, chars, options);
+        if let Some(diagnostic) = diagnostics.into_iter().next() {
+            return Err(diagnostic);
+        }
+
+        for col in left..left + chars.len() {
+            if let Cell::Part(id) = self.grid[(row, col)] {
+                self.parts[id.0] = None;
+            }
+        }
+
+        let mut col = left;
+        for token in tokens {
+            col += self.place_token(row, col, token);
+        }
+
+        Ok(())
+    }
+
+    // writes a token starting at `(row, col)` and returns how many columns it used
+    fn place_token(&mut self, row: usize, col: usize, token: Token) -> usize {
+        match token {
+            Token::Blank | Token::Unknown => {
+                self.grid[(row, col)] = Cell::Blank;
+                1
+            }
+            Token::Symbol(c) => {
+                self.grid[(row, col)] = Cell::Symbol(c);
+                1
+            }
+            Token::Number { text, value } => {
+                let len = text.chars().count();
+                let id = PartId(self.parts.len());
+                self.parts.push(Some(PartNumber {
+                    id,
+                    value,
+                    row,
+                    col_span: col..col + len,
+                    text,
+                }));
+                for c in col..col + len {
+                    self.grid[(row, c)] = Cell::Part(id);
+                }
+                len
+            }
+        }
+    }
+}
+
+/// Reads a number token like `12`, `-12` or `0x1F`.
+pub fn parse_number(token: &str) -> Option<i64> {
+    let (negative, digits) = match token.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, token),
+    };
+
+    let magnitude = match digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+    {
+        Some(hex) => i64::from_str_radix(hex, 16).ok()?,
+        None => digits.parse::<i64>().ok()?,
+    };
+
+    Some(if negative { -magnitude } else { magnitude })
+}
+
+/// Decides which characters in a schematic count as symbols.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum SymbolPolicy {
+    /// anything that is not a digit, a `.` or whitespace
+    #[default]
+    AnyNonDigit,
+    /// only the listed characters, everything else is reported
+    Only(String),
+}
+
+impl SymbolPolicy {
+    pub fn is_symbol(&self, c: char) -> bool {
+        if c.is_ascii_digit() || c == '.' || c.is_whitespace() {
+            return false;
+        }
+
+        match self {
+            SymbolPolicy::AnyNonDigit => true,
+            SymbolPolicy::Only(symbols) => symbols.contains(c),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    pub symbols: SymbolPolicy,
+    /// a `-` directly before digits makes the number negative instead of being a symbol
+    pub signed_numbers: bool,
+    /// `0x` followed by hex digits is read as one number
+    pub hex_numbers: bool,
+}
+
+/// Something the parser could not read; its cells are kept as blanks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Diagnostic {
+    UnknownCharacter {
+        row: usize,
+        col: usize,
+        found: char,
+    },
+    // a number starting at `col` whose value does not fit in an i64
+    NumberOutOfRange {
+        row: usize,
+        col: usize,
+        text: String,
+    },
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Diagnostic::UnknownCharacter { row, col, found } => write!(
+                f,
+                "unknown character {:?} at row {}, column {}",
+                found, row, col
+            ),
+            Diagnostic::NumberOutOfRange { row, col, text } => write!(
+                f,
+                "number {} at row {}, column {} does not fit in an i64",
+                text, row, col
+            ),
+        }
+    }
+}
+
+// numbers take one column per character, everything else takes one column
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Blank,
+    Symbol(char),
+    Number { text: String, value: i64 },
+    Unknown,
+}
+
+// how many characters of a number start at `chars[i]`, 0 when none does
+fn number_len(chars: &[char], i: usize, options: &ParseOptions) -> usize {
+    let sign = if options.signed_numbers && chars[i] == '-' {
+        1
+    } else {
+        0
+    };
+    let rest = &chars[i + sign..];
+
+    let is_hex = options.hex_numbers
+        && rest.len() > 2
+        && rest[0] == '0'
+        && (rest[1] == 'x' || rest[1] == 'X')
+        && rest[2].is_ascii_hexdigit();
+    if is_hex {
+        let digits = rest[2..]
+            .iter()
+            .take_while(|c| c.is_ascii_hexdigit())
+            .count();
+        return sign + 2 + digits;
+    }
+
+    let digits = rest.iter().take_while(|c| c.is_ascii_digit()).count();
+    if digits == 0 {
+        0
+    } else {
+        sign + digits
+    }
+}
+
+/// Whether `c` can be part of a number token under `options`.
+pub fn is_number_char(c: char, options: &ParseOptions) -> bool {
+    c.is_ascii_digit()
+        || (options.signed_numbers && c == '-')
+        || (options.hex_numbers && (c == 'x' || c == 'X' || c.is_ascii_hexdigit()))
+}
+
+/// Whether `c` can be placed in a schematic parsed with `options`.
+pub fn is_known(c: char, options: &ParseOptions) -> bool {
+    c.is_ascii_digit() || c == '.' || options.symbols.is_symbol(c)
+}
+
+// splits `chars`, which start at column `left` of `row`, into tokens along
+// with a diagnostic for everything that could not be read
+fn tokenize_row(
+    row: usize,
+    left: usize,
+    chars: &[char],
+    options: &ParseOptions,
+) -> (Vec<Token>, Vec<Diagnostic>) {
+    let mut tokens: Vec<Token> = vec![];
+    let mut diagnostics: Vec<Diagnostic> = vec![];
+
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+
+        let len = number_len(chars, i, options);
+        if len > 0 {
+            let text = chars[i..i + len].iter().collect::<String>();
+            match parse_number(&text) {
+                Some(value) => tokens.push(Token::Number { text, value }),
+                None => {
+                    diagnostics.push(Diagnostic::NumberOutOfRange {
+                        row,
+                        col: left + i,
+                        text,
+                    });
+                    tokens.extend(std::iter::repeat_n(Token::Unknown, len));
+                }
+            }
+            i += len;
+            continue;
+        }
+
+        let token = if c == '.' {
+            Token::Blank
+        } else if options.symbols.is_symbol(c) {
+            Token::Symbol(c)
+        } else {
+            // keep the column so everything after it stays aligned
+            diagnostics.push(Diagnostic::UnknownCharacter {
+                row,
+                col: left + i,
+                found: c,
+            });
+            Token::Unknown
+        };
+        tokens.push(token);
+        i += 1;
+    }
+
+    (tokens, diagnostics)
+}
+
+pub fn parse_grid(input: &str) -> Schematic {
+    let (schematic, _) = parse_grid_with(input, &ParseOptions::default());
+    schematic
+}
+
+pub fn parse_grid_with(input: &str, options: &ParseOptions) -> (Schematic, Vec<Diagnostic>) {
+    let lines: Vec<Vec<char>> = input
+        .trim()
+        .split('\n')
+        .map(|s| s.trim().chars().collect())
+        .collect();
+
+    // short rows are padded with blanks so the grid stays rectangular
+    let width = lines.iter().map(|l| l.len()).max().unwrap_or(0);
+
+    let mut schematic = Schematic {
+        grid: Grid::new(width, lines.len(), Cell::Blank),
+        parts: vec![],
+    };
+    let mut diagnostics: Vec<Diagnostic> = vec![];
+
+    for (row, line) in lines.iter().enumerate() {
+        let (tokens, found) = tokenize_row(row, 0, line, options);
+        diagnostics.extend(found);
+
+        let mut col = 0;
+        for token in tokens {
+            col += schematic.place_token(row, col, token);
+        }
+    }
+
+    (schematic, diagnostics)
+}
+
+/// Whether any cell of `part` has a symbol in `neighborhood`.
+pub fn touches_symbol(
+    schematic: &Schematic,
+    part: &PartNumber,
+    neighborhood: &Neighborhood,
+) -> bool {
+    part.col_span.clone().any(|col| {
+        schematic
+            .grid
+            .neighbors_in(part.row, col, neighborhood)
+            .any(|(_, _, cell)| matches!(cell, Cell::Symbol(_)))
+    })
+}
+
+pub fn find_adjacent_cells(schematic: &Schematic) -> Vec<&PartNumber> {
+    find_adjacent_cells_with(schematic, &Neighborhood::default())
+}
+
+/// Part numbers touching a symbol anywhere in `neighborhood`, ordered by id.
+pub fn find_adjacent_cells_with<'a>(
+    schematic: &'a Schematic,
+    neighborhood: &Neighborhood,
+) -> Vec<&'a PartNumber> {
+    schematic
+        .parts()
+        .filter(|part| touches_symbol(schematic, part, neighborhood))
+        .collect()
+}
+
+// totals are added up as i128, which no grid of i64 parts can overflow,
+// and clamped to the i64 range at the end
+pub(crate) fn saturate(total: i128) -> i64 {
+    total.clamp(i64::MIN.into(), i64::MAX.into()) as i64
+}
+
+/// The sum of `parts`, clamped to `i64::MIN..=i64::MAX` if it does not fit.
+pub fn find_signature(parts: Vec<&PartNumber>) -> i64 {
+    saturate(parts.into_iter().map(|p| i128::from(p.value)).sum())
+}
+
+/// The distinct part numbers touching `(row, col)`, ordered by id.
+pub fn find_adjacent_parts(schematic: &Schematic, row: usize, col: usize) -> Vec<&PartNumber> {
+    find_adjacent_parts_with(schematic, row, col, &Neighborhood::default())
+}
+
+pub fn find_adjacent_parts_with<'a>(
+    schematic: &'a Schematic,
+    row: usize,
+    col: usize,
+    neighborhood: &Neighborhood,
+) -> Vec<&'a PartNumber> {
+    let mut ids = schematic
+        .grid
+        .neighbors_in(row, col, neighborhood)
+        .filter_map(|(_, _, cell)| match cell {
+            Cell::Part(id) => Some(*id),
+            _ => None,
+        })
+        .collect::<Vec<PartId>>();
+    ids.sort();
+    ids.dedup();
+
+    ids.into_iter()
+        .filter_map(|id| schematic.part(id))
+        .collect()
+}
+
+pub fn find_gear_ratio(schematic: &Schematic) -> i64 {
+    find_gear_value(schematic, &default_rules())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        grid::Neighborhood,
+        schematic::{
+            find_adjacent_cells, find_adjacent_cells_with, find_adjacent_parts, find_signature,
+            parse_grid, parse_grid_with, parse_number, Cell, Diagnostic, ParseOptions, PartId,
+            PartNumber, SymbolPolicy,
+        },
+    };
+
+    #[test]
+    fn it_parses_each_number_once() {
+        let input = "
+        ..123.4
+        .....*.
+        7......"
+            .trim();
+
+        let schematic = parse_grid(input);
+
+        let parts = schematic.parts().cloned().collect::<Vec<PartNumber>>();
+        assert_eq!(
+            parts,
+            vec![
+                PartNumber {
+                    id: PartId(0),
+                    value: 123,
+                    row: 0,
+                    col_span: 2..5,
+                    text: "123".to_string(),
+                },
+                PartNumber {
+                    id: PartId(1),
+                    value: 4,
+                    row: 0,
+                    col_span: 6..7,
+                    text: "4".to_string(),
+                },
+                PartNumber {
+                    id: PartId(2),
+                    value: 7,
+                    row: 2,
+                    col_span: 0..1,
+                    text: "7".to_string(),
+                },
+            ]
+        );
+
+        // every cell of 123 refers to the same part
+        for col in 2..5 {
+            assert_eq!(schematic.grid()[(0, col)], Cell::Part(PartId(0)));
+        }
+        assert_eq!(schematic.glyph(0, 3), '2');
+
+        // the '*' touches two cells of 123 but sees it once
+        let around = find_adjacent_parts(&schematic, 1, 5);
+        assert_eq!(
+            around.iter().map(|p| p.id).collect::<Vec<PartId>>(),
+            vec![PartId(0), PartId(1)]
+        );
+    }
+
+    #[test]
+    fn it_treats_any_non_digit_as_symbol() {
+        let input = "
+        ..12.~..
+        ?.......
+        .7|3...."
+            .trim();
+
+        let schematic = parse_grid(input);
+
+        assert_eq!(schematic.grid()[(0, 5)], Cell::Symbol('~'));
+        assert_eq!(schematic.grid()[(1, 0)], Cell::Symbol('?'));
+        assert_eq!(schematic.grid()[(2, 2)], Cell::Symbol('|'));
+
+        let actual = find_adjacent_cells(&schematic)
+            .into_iter()
+            .map(|p| (p.row, p.col_span.start, p.value))
+            .collect::<Vec<(usize, usize, i64)>>();
+
+        assert_eq!(actual, vec![(2, 1, 7), (2, 3, 3)]);
+    }
+
+    #[test]
+    fn it_reports_unknown_characters() {
+        let input = "
+        ..12~*..
+        ?......."
+            .trim();
+
+        let options = ParseOptions {
+            symbols: SymbolPolicy::Only("*".to_string()),
+            ..Default::default()
+        };
+
+        let (schematic, diagnostics) = parse_grid_with(input, &options);
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic::UnknownCharacter {
+                    row: 0,
+                    col: 4,
+                    found: '~',
+                },
+                Diagnostic::UnknownCharacter {
+                    row: 1,
+                    col: 0,
+                    found: '?',
+                },
+            ]
+        );
+
+        // the unknown character keeps its column, so '*' stays at 5
+        assert_eq!(schematic.width(), 8);
+        assert_eq!(schematic.grid()[(0, 4)], Cell::Blank);
+        assert_eq!(schematic.grid()[(0, 5)], Cell::Symbol('*'));
+    }
+
+    #[test]
+    fn it_reports_numbers_out_of_range() {
+        let input = "99999999999999999999*12";
+
+        let (schematic, diagnostics) = parse_grid_with(input, &ParseOptions::default());
+
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::NumberOutOfRange {
+                row: 0,
+                col: 0,
+                text: "99999999999999999999".to_string(),
+            }]
+        );
+        assert_eq!(schematic.grid()[(0, 0)], Cell::Blank);
+        assert_eq!(schematic.grid()[(0, 20)], Cell::Symbol('*'));
+        assert_eq!(find_signature(find_adjacent_cells(&parse_grid(input))), 12);
+    }
+
+    #[test]
+    fn it_saturates_the_part_sum() {
+        let schematic = parse_grid("9223372036854775807*1");
+
+        assert_eq!(find_signature(find_adjacent_cells(&schematic)), i64::MAX);
+    }
+
+    #[test]
+    fn it_finds_adjacent_cells_across_wrapped_edges() {
+        let input = "
+        7...*
+        .....
+        ....."
+            .trim();
+
+        let schematic = parse_grid(input);
+
+        let wrapped = Neighborhood::moore().wrapping();
+
+        assert!(find_adjacent_cells(&schematic).is_empty());
+
+        let actual = find_adjacent_cells_with(&schematic, &wrapped)
+            .into_iter()
+            .map(|p| (p.row, p.col_span.start, p.value))
+            .collect::<Vec<(usize, usize, i64)>>();
+
+        assert_eq!(actual, vec![(0, 0, 7)]);
+    }
+
+    #[test]
+    fn it_parses_number_tokens() {
+        assert_eq!(parse_number("12"), Some(12));
+        assert_eq!(parse_number("-12"), Some(-12));
+        assert_eq!(parse_number("0x1F"), Some(31));
+        assert_eq!(parse_number("-0x1f"), Some(-31));
+        assert_eq!(parse_number("x"), None);
+    }
+
+    #[test]
+    fn it_reads_signed_and_hex_numbers() {
+        let input = "
+        -12..0x1F..
+        ...*#......
+        .......5-3."
+            .trim();
+
+        let options = ParseOptions {
+            signed_numbers: true,
+            hex_numbers: true,
+            ..Default::default()
+        };
+
+        let (schematic, diagnostics) = parse_grid_with(input, &options);
+        assert!(diagnostics.is_empty());
+
+        assert_eq!(schematic.part_at(0, 0).unwrap().text, "-12");
+        assert_eq!(schematic.part_at(0, 6).unwrap().text, "0x1F");
+        assert_eq!(schematic.part_at(0, 6).unwrap().value, 31);
+
+        // a minus directly attached to digits joins them
+        assert_eq!(schematic.part_at(2, 9).unwrap().text, "-3");
+
+        // '*' touches -12 and '#' touches 0x1F, 5 and -3 touch nothing
+        let adjacent = find_adjacent_cells(&schematic);
+        assert_eq!(find_signature(adjacent), -12 + 31);
+    }
+
+    #[test]
+    fn it_keeps_minus_as_a_symbol_by_default() {
+        let input = "
+        -12..
+        ....."
+            .trim();
+
+        let schematic = parse_grid(input);
+
+        assert_eq!(schematic.grid()[(0, 0)], Cell::Symbol('-'));
+        assert_eq!(schematic.part_at(0, 1).unwrap().value, 12);
+
+        let adjacent = find_adjacent_cells(&schematic);
+        assert_eq!(find_signature(adjacent), 12);
+    }
+}
@@ -0,0 +1,55 @@
+use std::collections::HashSet;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use day_04::{
+    cards::{find_match_count, Card},
+    generate::{generate_deck, DeckConfig},
+    numbers::PackedCard,
+};
+
+// `find_match_count` before `NumberSet`: both sides cloned into a
+// HashSet on every call
+fn hash_set_match_count(card: &Card) -> u32 {
+    let winning_set: HashSet<u32> = card.winning.clone().into_iter().collect();
+    let drawn_set: HashSet<u32> = card.drawn.clone().into_iter().collect();
+
+    drawn_set.intersection(&winning_set).count() as u32
+}
+
+fn bench_matching(c: &mut Criterion) {
+    let config = DeckConfig {
+        count: 1_000_000,
+        ..Default::default()
+    };
+    let cards = generate_deck(&config, 42);
+    let packed = cards
+        .iter()
+        .map(PackedCard::from)
+        .collect::<Vec<PackedCard>>();
+
+    let mut group = c.benchmark_group("match_count");
+    group.sample_size(10);
+    group.bench_function("hash_set", |b| {
+        b.iter(|| {
+            black_box(&cards)
+                .iter()
+                .map(hash_set_match_count)
+                .sum::<u32>()
+        })
+    });
+    group.bench_function("number_set", |b| {
+        b.iter(|| black_box(&cards).iter().map(find_match_count).sum::<u32>())
+    });
+    group.bench_function("packed", |b| {
+        b.iter(|| {
+            black_box(&packed)
+                .iter()
+                .map(|card| card.match_count())
+                .sum::<u32>()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_matching);
+criterion_main!(benches);
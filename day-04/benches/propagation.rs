@@ -0,0 +1,48 @@
+use std::collections::BTreeMap;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use day_04::cards::{find_derived_cards, find_match_count, get_cards, Card};
+
+// the per-copy loop `find_derived_cards` replaced: every copy of a card
+// walks its earned cards again, so the work grows with the copy count
+fn per_copy_derived_cards(cards: Vec<Card>) -> BTreeMap<u32, u32> {
+    let mut map: BTreeMap<u32, u32> = BTreeMap::new();
+
+    for card in cards.iter() {
+        map.insert(card.id, 1);
+    }
+
+    for card in cards.into_iter() {
+        let id_as_number = card.id;
+        let match_count = find_match_count(&card);
+
+        let copies_of_current_card = map[&id_as_number];
+
+        for _ in 0..copies_of_current_card {
+            let earned_range = (id_as_number + 1)..(id_as_number + match_count + 1);
+            for earned_card in earned_range {
+                *map.get_mut(&earned_card).unwrap() += 1;
+            }
+        }
+    }
+
+    map
+}
+
+fn bench_propagation(c: &mut Criterion) {
+    let input = include_str!("../src/bin/input1.txt").trim();
+    let cards = get_cards(input).unwrap();
+
+    let mut group = c.benchmark_group("find_derived_cards");
+    group.sample_size(10);
+    group.bench_function("per_copy", |b| {
+        b.iter(|| per_copy_derived_cards(black_box(cards.clone())).len())
+    });
+    group.bench_function("per_card", |b| {
+        b.iter(|| find_derived_cards(black_box(cards.clone())).unwrap().len())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_propagation);
+criterion_main!(benches);
@@ -1,102 +1,56 @@
-use std::{cmp, collections::HashSet};
+use day_04::{
+    cards::{find_signature, get_cards, MatchPolicy},
+    scoring::{find_scores_with, ScoringRule},
+    validate::validate_cards,
+};
 
 fn main() {
+    // cargo run --bin part1 -- --scoring fibonacci --multiset
     let input = include_str!("input1.txt").trim();
 
-    let scores = get_cards(input)
-        .into_iter()
-        .map(|card| get_score_from_card(card))
-        .collect::<Vec<u32>>();
-
-    let answer = find_signature(scores);
-    println!("answer = {}", answer);
-    // 18519
-}
-
-#[derive(Debug, PartialEq, Eq, Hash)]
-struct Card {
-    id: String,
-    winning: Vec<u32>,
-    drawn: Vec<u32>,
-}
-
-fn parse_line(input: &str) -> Card {
-    let part = input.split(":").map(|f| f.trim()).collect::<Vec<&str>>();
-    let card_part = part.first().unwrap();
-    let card_id = card_part.replace("Card", "").trim().to_string();
-
-    let numbers_part = part.last().unwrap();
-    let number_parts = numbers_part
-        .split("|")
-        .map(|f| f.trim())
-        .collect::<Vec<&str>>();
-
-    let winning_part = number_parts.first().unwrap();
-    let drawn_part = number_parts.last().unwrap();
-
-    let winning_numbers = winning_part
-        .split_whitespace()
-        .map(|f| f.trim())
-        .map(|f| f.parse::<u32>().unwrap())
-        .collect::<Vec<u32>>();
-
-    let drawn_numbers = drawn_part
-        .split_whitespace()
-        .map(|f| f.trim())
-        .map(|f| f.parse::<u32>().unwrap())
-        .collect::<Vec<u32>>();
-
-    return Card {
-        id: card_id,
-        winning: winning_numbers,
-        drawn: drawn_numbers,
+    let args = std::env::args().collect::<Vec<String>>();
+    let rule = match args.iter().position(|arg| arg == "--scoring") {
+        Some(i) => match args.get(i + 1).map_or("", |f| f.as_str()).parse() {
+            Ok(rule) => rule,
+            Err(err) => {
+                eprintln!("error: --scoring: {}", err);
+                std::process::exit(1);
+            }
+        },
+        None => ScoringRule::default(),
     };
-}
-
-fn get_cards(input: &str) -> Vec<Card> {
-    let lines = input.split("\n").map(|f| f.trim()).collect::<Vec<&str>>();
-
-    let cards: Vec<Card> = lines.into_iter().map(|f| parse_line(f)).collect();
 
-    return cards;
-}
-
-fn get_score_from_card(card: Card) -> u32 {
-    // intersection
-    let winning_set: HashSet<u32> = card.winning.into_iter().collect();
-    let drawn_set: HashSet<u32> = card.drawn.into_iter().collect();
-
-    let intersection = drawn_set.intersection(&winning_set);
-
-    let intersection_list = intersection.into_iter().collect::<Vec<&u32>>();
-    let intersection_len = intersection_list.len();
-
-    let score = if intersection_len == 0 {
-        0
+    let policy = if args.iter().any(|arg| arg == "--multiset") {
+        MatchPolicy::Multiset
     } else {
-        2_i32.pow(cmp::max(intersection_len as i32 - 1_i32, 0_i32) as u32)
+        MatchPolicy::Set
     };
 
-    return score as u32;
-}
+    let cards = get_cards(input).unwrap();
+    for issue in validate_cards(&cards) {
+        eprintln!("warning: {}", issue);
+    }
+
+    let scores = find_scores_with(&cards, &rule, policy);
 
-fn find_signature(scores: Vec<u32>) -> u32 {
-    return scores.into_iter().sum();
+    let answer = find_signature(scores);
+    println!("answer = {}", answer);
+    // 18519
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{find_signature, get_cards, get_score_from_card, parse_line, Card};
+    use day_04::cards::{find_signature, get_cards, get_score_from_card, parse_line, Card};
 
     #[test]
     fn it_parse_line() {
         let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53".trim();
         let expected = Card {
-            id: "1".to_string(),
+            id: 1,
             winning: vec![41, 48, 83, 86, 17],
             drawn: vec![83, 86, 6, 31, 17, 9, 48, 53],
         };
-        let actual = parse_line(input);
+        let actual = parse_line(input).unwrap();
 
         assert_eq!(actual, expected);
     }
@@ -105,7 +59,7 @@ mod tests {
     fn it_get_score_from_card1() {
         let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53".trim();
 
-        let card = parse_line(input);
+        let card = parse_line(input).unwrap();
         let actual = get_score_from_card(card);
 
         let expected = 8;
@@ -117,7 +71,7 @@ mod tests {
     fn it_get_score_from_card2() {
         let input = "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19".trim();
 
-        let card = parse_line(input);
+        let card = parse_line(input).unwrap();
         let actual = get_score_from_card(card);
 
         let expected = 2;
@@ -132,17 +86,17 @@ mod tests {
             .trim();
         let expected = vec![
             Card {
-                id: "1".to_string(),
+                id: 1,
                 winning: vec![41, 48, 83, 86, 17],
                 drawn: vec![83, 86, 6, 31, 17, 9, 48, 53],
             },
             Card {
-                id: "2".to_string(),
+                id: 2,
                 winning: vec![13, 32, 20, 16, 61],
                 drawn: vec![61, 30, 68, 82, 17, 32, 24, 19],
             },
         ];
-        let actual = get_cards(input);
+        let actual = get_cards(input).unwrap();
 
         assert_eq!(actual, expected);
     }
@@ -159,6 +113,7 @@ mod tests {
             .trim();
 
         let scores = get_cards(input)
+            .unwrap()
             .into_iter()
             .map(|card| get_score_from_card(card))
             .collect::<Vec<u32>>();
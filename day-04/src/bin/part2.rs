@@ -1,152 +1,40 @@
-use log::{debug, info};
-use std::{
-    cmp,
-    collections::{BTreeMap, HashSet},
+use day_04::{
+    cards::{find_derived_cards_with, get_cards, MatchPolicy, MissingCardPolicy},
+    trace::trace_derived_cards,
 };
 
 fn main() {
+    // cargo run --bin part2 -- --trace table|json|dot --multiset
     let input = include_str!("input1.txt").trim();
 
-    let original_cards = get_cards(input);
-
-    let map = find_derived_cards(original_cards);
-
-    let sum = map.values().sum::<u32>();
-
-    println!("answer map = {:#?}", map);
-    println!("answer sum = {}", sum);
-    // 11787590
-}
-
-#[derive(Debug, PartialEq, Eq, Hash)]
-struct Card {
-    id: String,
-    winning: Vec<u32>,
-    drawn: Vec<u32>,
-}
-
-fn parse_line(input: &str) -> Card {
-    let part = input.split(":").map(|f| f.trim()).collect::<Vec<&str>>();
-    let card_part = part.first().unwrap();
-    let card_id = card_part.replace("Card", "").trim().to_string();
-
-    let numbers_part = part.last().unwrap();
-    let number_parts = numbers_part
-        .split("|")
-        .map(|f| f.trim())
-        .collect::<Vec<&str>>();
-
-    let winning_part = number_parts.first().unwrap();
-    let drawn_part = number_parts.last().unwrap();
-
-    let winning_numbers = winning_part
-        .split_whitespace()
-        .map(|f| f.trim())
-        .map(|f| f.parse::<u32>().unwrap())
-        .collect::<Vec<u32>>();
-
-    let drawn_numbers = drawn_part
-        .split_whitespace()
-        .map(|f| f.trim())
-        .map(|f| f.parse::<u32>().unwrap())
-        .collect::<Vec<u32>>();
-
-    return Card {
-        id: card_id,
-        winning: winning_numbers,
-        drawn: drawn_numbers,
-    };
-}
-
-fn get_cards(input: &str) -> Vec<Card> {
-    let lines = input.split("\n").map(|f| f.trim()).collect::<Vec<&str>>();
-
-    let cards: Vec<Card> = lines.into_iter().map(|f| parse_line(f)).collect();
-
-    return cards;
-}
-
-fn get_score_from_card(card: Card) -> u32 {
-    // intersection
-    let winning_set: HashSet<u32> = card.winning.into_iter().collect();
-    let drawn_set: HashSet<u32> = card.drawn.into_iter().collect();
-
-    let intersection = drawn_set.intersection(&winning_set);
-
-    let intersection_list = intersection.into_iter().collect::<Vec<&u32>>();
-    let intersection_len = intersection_list.len();
-
-    let score = if intersection_len == 0 {
-        0
+    let args = std::env::args().collect::<Vec<String>>();
+    let policy = if args.iter().any(|arg| arg == "--multiset") {
+        MatchPolicy::Multiset
     } else {
-        2_i32.pow(cmp::max(intersection_len as i32 - 1_i32, 0_i32) as u32)
+        MatchPolicy::Set
     };
 
-    return score as u32;
-}
-
-fn find_match_count(card: &Card) -> u32 {
-    let winning_set: HashSet<u32> = card.winning.clone().into_iter().collect();
-    let drawn_set: HashSet<u32> = card.drawn.clone().into_iter().collect();
-
-    let intersection = drawn_set.intersection(&winning_set);
-
-    let intersection_list = intersection.into_iter().collect::<Vec<&u32>>();
-    let intersection_len = intersection_list.len();
-
-    return intersection_len as u32;
-}
-
-fn find_signature(scores: Vec<u32>) -> u32 {
-    return scores.into_iter().sum();
-}
-
-fn find_derived_cards(cards: Vec<Card>) -> BTreeMap<u32, u32> {
-    // BTreeMap is an ordered Map
-    // https://doc.rust-lang.org/stable/std/collections/struct.BTreeMap.html
-    // this really only for debugging purposes
-    let mut map: BTreeMap<u32, u32> = BTreeMap::new();
-
-    // prime the cards initially
-    for card in cards.iter() {
-        let id_as_number = card.id.clone().parse::<u32>().unwrap();
-        map.insert(id_as_number, 1);
+    if let Some(i) = args.iter().position(|arg| arg == "--trace") {
+        let trace =
+            trace_derived_cards(get_cards(input).unwrap(), MissingCardPolicy::Error, policy)
+                .unwrap();
+        match args.get(i + 1).map(|f| f.as_str()) {
+            Some("json") => print!("{}", trace.to_json()),
+            Some("dot") => print!("{}", trace.to_dot()),
+            _ => print!("{}", trace.to_table()),
+        }
+        return;
     }
 
-    // each iteration should do things to the sums of cards
-    for card in cards.into_iter() {
-        let id_as_number = card.id.clone().parse::<u32>().unwrap();
+    let original_cards = get_cards(input).unwrap();
 
-        let match_count = find_match_count(&card);
+    let map = find_derived_cards_with(original_cards, MissingCardPolicy::Error, policy).unwrap();
 
-        if match_count == 0 {
-            continue;
-        }
+    let sum = map.values().sum::<u64>();
 
-        let copies_of_current_card = map.get(&id_as_number).unwrap().clone();
-
-        debug!("card {} has {} matches", id_as_number, match_count);
-
-        debug!(
-            "card {} has {} copies",
-            id_as_number, copies_of_current_card
-        );
-
-        for _ in 0..copies_of_current_card {
-            let earned_range = (id_as_number + 1)..(id_as_number + match_count + 1);
-            for earned_card in earned_range {
-                let current_value = map.get(&earned_card).unwrap();
-                let next_value = current_value + 1;
-                debug!(
-                    "earning copy of card {}; {} → {}",
-                    earned_card, current_value, next_value
-                );
-                map.insert(earned_card, next_value);
-            }
-        }
-    }
-
-    return map;
+    println!("answer map = {:#?}", map);
+    println!("answer sum = {}", sum);
+    // 11787590
 }
 
 #[cfg(test)]
@@ -155,7 +43,7 @@ mod tests {
 
     use log::debug;
 
-    use crate::{
+    use day_04::cards::{
         find_derived_cards, find_signature, get_cards, get_score_from_card, parse_line, Card,
     };
 
@@ -163,11 +51,11 @@ mod tests {
     fn it_parse_line() {
         let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53".trim();
         let expected = Card {
-            id: "1".to_string(),
+            id: 1,
             winning: vec![41, 48, 83, 86, 17],
             drawn: vec![83, 86, 6, 31, 17, 9, 48, 53],
         };
-        let actual = parse_line(input);
+        let actual = parse_line(input).unwrap();
 
         assert_eq!(actual, expected);
     }
@@ -176,7 +64,7 @@ mod tests {
     fn it_get_score_from_card1() {
         let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53".trim();
 
-        let card = parse_line(input);
+        let card = parse_line(input).unwrap();
         let actual = get_score_from_card(card);
 
         let expected = 8;
@@ -188,7 +76,7 @@ mod tests {
     fn it_get_score_from_card2() {
         let input = "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19".trim();
 
-        let card = parse_line(input);
+        let card = parse_line(input).unwrap();
         let actual = get_score_from_card(card);
 
         let expected = 2;
@@ -203,17 +91,17 @@ mod tests {
             .trim();
         let expected = vec![
             Card {
-                id: "1".to_string(),
+                id: 1,
                 winning: vec![41, 48, 83, 86, 17],
                 drawn: vec![83, 86, 6, 31, 17, 9, 48, 53],
             },
             Card {
-                id: "2".to_string(),
+                id: 2,
                 winning: vec![13, 32, 20, 16, 61],
                 drawn: vec![61, 30, 68, 82, 17, 32, 24, 19],
             },
         ];
-        let actual = get_cards(input);
+        let actual = get_cards(input).unwrap();
 
         assert_eq!(actual, expected);
     }
@@ -230,6 +118,7 @@ mod tests {
             .trim();
 
         let scores = get_cards(input)
+            .unwrap()
             .into_iter()
             .map(|card| get_score_from_card(card))
             .collect::<Vec<u32>>();
@@ -251,11 +140,11 @@ mod tests {
         Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"
             .trim();
 
-        let original_cards = get_cards(input);
+        let original_cards = get_cards(input).unwrap();
 
-        let map = find_derived_cards(original_cards);
+        let map = find_derived_cards(original_cards).unwrap();
 
-        let sum = map.values().sum::<u32>();
+        let sum = map.values().sum::<u64>();
 
         let expected_sum = 30;
 
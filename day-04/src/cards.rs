@@ -0,0 +1,405 @@
+use log::debug;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt,
+};
+
+use crate::{numbers::NumberSet, scoring::ScoringRule};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Card {
+    pub id: u32,
+    pub winning: Vec<u32>,
+    pub drawn: Vec<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CardError {
+    ExpectedCardKeyword(String),
+    ExpectedColon(String),
+    ExpectedBar(String),
+    InvalidId(String),
+    InvalidNumber(String),
+    DuplicateId(u32),
+    // `from` would earn a copy of `earned`, which has no card
+    MissingCard { from: u32, earned: u32 },
+    // `from` would earn the card `offset` ids after it, past `u32::MAX`
+    IdOverflow { from: u32, offset: u32 },
+    CountOverflow(u32),
+}
+
+impl fmt::Display for CardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CardError::ExpectedCardKeyword(line) => write!(f, "expected \"Card\" in {:?}", line),
+            CardError::ExpectedColon(line) => write!(f, "expected \":\" in {:?}", line),
+            CardError::ExpectedBar(line) => write!(f, "expected \"|\" in {:?}", line),
+            CardError::InvalidId(id) => write!(f, "card id {:?} is not a number", id),
+            CardError::InvalidNumber(n) => write!(f, "{:?} is not a number", n),
+            CardError::DuplicateId(id) => write!(f, "card {} appears more than once", id),
+            CardError::MissingCard { from, earned } => {
+                write!(
+                    f,
+                    "card {} earns card {}, which does not exist",
+                    from, earned
+                )
+            }
+            CardError::IdOverflow { from, offset } => write!(
+                f,
+                "card {} earns card {} + {}, which is past u32::MAX",
+                from, from, offset
+            ),
+            CardError::CountOverflow(id) => write!(f, "copies of card {} overflowed u64", id),
+        }
+    }
+}
+
+impl std::error::Error for CardError {}
+
+/// What to do when a card earns copies of a card id that is not in the deck.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MissingCardPolicy {
+    /// stop with `CardError::MissingCard`
+    #[default]
+    Error,
+    /// skip the missing ids, so earnings past the last card are dropped
+    Clamp,
+    /// add the missing card with only its earned copies; it earns nothing itself
+    Create,
+}
+
+fn parse_numbers(input: &str) -> Result<Vec<u32>, CardError> {
+    input
+        .split_whitespace()
+        .map(|f| {
+            f.parse::<u32>()
+                .map_err(|_| CardError::InvalidNumber(f.to_string()))
+        })
+        .collect()
+}
+
+pub fn parse_line(input: &str) -> Result<Card, CardError> {
+    let (card_part, numbers_part) = input
+        .split_once(':')
+        .ok_or_else(|| CardError::ExpectedColon(input.to_string()))?;
+
+    let card_id = card_part
+        .trim()
+        .strip_prefix("Card")
+        .ok_or_else(|| CardError::ExpectedCardKeyword(input.to_string()))?
+        .trim();
+    let id = card_id
+        .parse::<u32>()
+        .map_err(|_| CardError::InvalidId(card_id.to_string()))?;
+
+    let (winning_part, drawn_part) = numbers_part
+        .split_once('|')
+        .ok_or_else(|| CardError::ExpectedBar(input.to_string()))?;
+
+    Ok(Card {
+        id,
+        winning: parse_numbers(winning_part)?,
+        drawn: parse_numbers(drawn_part)?,
+    })
+}
+
+/// Parses every line, card ids must be unique but may have gaps.
+pub fn get_cards(input: &str) -> Result<Vec<Card>, CardError> {
+    let lines = input.split('\n').map(|f| f.trim()).collect::<Vec<&str>>();
+
+    let mut seen: HashSet<u32> = HashSet::new();
+    let mut cards: Vec<Card> = vec![];
+
+    for line in lines.into_iter() {
+        let card = parse_line(line)?;
+        if !seen.insert(card.id) {
+            return Err(CardError::DuplicateId(card.id));
+        }
+        cards.push(card);
+    }
+
+    Ok(cards)
+}
+
+/// The puzzle's score, see `ScoringRule` for the other variants.
+pub fn get_score_from_card(card: Card) -> u32 {
+    ScoringRule::Doubling.score(find_match_count(&card))
+}
+
+/// How repeated numbers on a card are matched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatchPolicy {
+    /// each number matches at most once, however often it repeats
+    #[default]
+    Set,
+    /// a number repeated on both sides matches once per pair, so
+    /// `5 5 | 5 5 5` is two matches
+    Multiset,
+}
+
+pub fn find_match_count(card: &Card) -> u32 {
+    let winning_set = NumberSet::from_numbers(&card.winning);
+    let drawn_set = NumberSet::from_numbers(&card.drawn);
+
+    drawn_set.intersection_len(&winning_set)
+}
+
+pub fn find_match_count_with(card: &Card, policy: MatchPolicy) -> u32 {
+    match policy {
+        MatchPolicy::Set => find_match_count(card),
+        MatchPolicy::Multiset => {
+            let mut winning: HashMap<u32, u32> = HashMap::new();
+            for n in card.winning.iter() {
+                *winning.entry(*n).or_insert(0) += 1;
+            }
+
+            let mut count = 0;
+            for n in card.drawn.iter() {
+                if let Some(left) = winning.get_mut(n).filter(|left| **left > 0) {
+                    *left -= 1;
+                    count += 1;
+                }
+            }
+            count
+        }
+    }
+}
+
+/// The sum of `scores`, saturating at `u32::MAX` like the scores themselves.
+pub fn find_signature(scores: Vec<u32>) -> u32 {
+    scores
+        .into_iter()
+        .fold(0, |sum, score| sum.saturating_add(score))
+}
+
+/// How many copies of each card you end up with, keyed by card id;
+/// earning a card that does not exist is an error.
+pub fn find_derived_cards(cards: Vec<Card>) -> Result<BTreeMap<u32, u64>, CardError> {
+    find_derived_cards_with(cards, MissingCardPolicy::Error, MatchPolicy::Set)
+}
+
+/// Each card adds all of its copies to the cards it earns in one step, so the
+/// work grows with the number of cards rather than the number of copies.
+pub fn find_derived_cards_with(
+    cards: Vec<Card>,
+    policy: MissingCardPolicy,
+    match_policy: MatchPolicy,
+) -> Result<BTreeMap<u32, u64>, CardError> {
+    propagate(cards, policy, match_policy, |_, _, _| {})
+}
+
+// the propagation itself, `on_earn(from, earned, copies)` sees every step
+pub(crate) fn propagate(
+    cards: Vec<Card>,
+    policy: MissingCardPolicy,
+    match_policy: MatchPolicy,
+    mut on_earn: impl FnMut(u32, u32, u64),
+) -> Result<BTreeMap<u32, u64>, CardError> {
+    // BTreeMap is an ordered Map
+    // https://doc.rust-lang.org/stable/std/collections/struct.BTreeMap.html
+    // this really only for debugging purposes
+    let mut map: BTreeMap<u32, u64> = BTreeMap::new();
+
+    // prime the cards initially
+    for card in cards.iter() {
+        map.insert(card.id, 1);
+    }
+
+    // copies only flow to higher ids, so lower ids must be settled first
+    let mut cards = cards;
+    cards.sort_by_key(|card| card.id);
+
+    // each iteration should do things to the sums of cards
+    for card in cards.into_iter() {
+        let match_count = find_match_count_with(&card, match_policy);
+
+        if match_count == 0 {
+            continue;
+        }
+
+        let copies_of_current_card = map[&card.id];
+
+        debug!("card {} has {} matches", card.id, match_count);
+
+        debug!("card {} has {} copies", card.id, copies_of_current_card);
+
+        for offset in 1..=match_count {
+            // ids past u32::MAX can never exist
+            let Some(earned_card) = card.id.checked_add(offset) else {
+                if policy == MissingCardPolicy::Clamp {
+                    break;
+                }
+                return Err(CardError::IdOverflow {
+                    from: card.id,
+                    offset,
+                });
+            };
+
+            let current_value = match (map.get_mut(&earned_card), policy) {
+                (Some(value), _) => value,
+                (None, MissingCardPolicy::Error) => {
+                    return Err(CardError::MissingCard {
+                        from: card.id,
+                        earned: earned_card,
+                    })
+                }
+                (None, MissingCardPolicy::Clamp) => continue,
+                (None, MissingCardPolicy::Create) => map.entry(earned_card).or_insert(0),
+            };
+
+            let next_value = current_value
+                .checked_add(copies_of_current_card)
+                .ok_or(CardError::CountOverflow(earned_card))?;
+            debug!(
+                "earning {} copies of card {}; {} → {}",
+                copies_of_current_card, earned_card, current_value, next_value
+            );
+            *current_value = next_value;
+            on_earn(card.id, earned_card, copies_of_current_card);
+        }
+    }
+
+    Ok(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::cards::{
+        find_derived_cards, find_derived_cards_with, find_match_count_with, get_cards, parse_line,
+        Card, CardError, MatchPolicy, MissingCardPolicy,
+    };
+
+    // card `i` matches the `matches[i]` cards after it
+    fn cards_with_matches(matches: &[u32]) -> Vec<Card> {
+        matches
+            .iter()
+            .enumerate()
+            .map(|(i, n)| Card {
+                id: i as u32 + 1,
+                winning: (1..=*n).collect(),
+                drawn: (1..=*n).collect(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_counts_past_u32() {
+        // every card earns every card after it, so card n ends with 2^(n-1) copies
+        let count = 40;
+        let matches = (0..count).rev().collect::<Vec<u32>>();
+
+        let map = find_derived_cards(cards_with_matches(&matches)).unwrap();
+
+        assert_eq!(map[&40], 1 << 39);
+        assert_eq!(map.values().sum::<u64>(), (1 << 40) - 1);
+    }
+
+    #[test]
+    fn it_rejects_malformed_lines() {
+        assert_eq!(
+            parse_line("Card x: 1 | 2"),
+            Err(CardError::InvalidId("x".to_string()))
+        );
+        assert_eq!(
+            parse_line("Card 1: 1 2"),
+            Err(CardError::ExpectedBar("Card 1: 1 2".to_string()))
+        );
+        assert_eq!(
+            parse_line("Card 1: 1 | 2 b"),
+            Err(CardError::InvalidNumber("b".to_string()))
+        );
+        assert_eq!(
+            get_cards("Card 1: 1 | 2\nCard 1: 3 | 4"),
+            Err(CardError::DuplicateId(1))
+        );
+    }
+
+    #[test]
+    fn it_applies_the_missing_card_policy() {
+        // card 2 earns 3 and 4, but 4 is past the last card
+        let input = "
+        Card 1: 1 | 1
+        Card 2: 1 2 | 1 2
+        Card 3: 5 | 6"
+            .trim();
+        let cards = get_cards(input).unwrap();
+
+        assert_eq!(
+            find_derived_cards(cards.clone()),
+            Err(CardError::MissingCard { from: 2, earned: 4 })
+        );
+
+        let clamped =
+            find_derived_cards_with(cards.clone(), MissingCardPolicy::Clamp, MatchPolicy::Set);
+        assert_eq!(clamped, Ok(BTreeMap::from([(1, 1), (2, 2), (3, 3)])));
+
+        let created = find_derived_cards_with(cards, MissingCardPolicy::Create, MatchPolicy::Set);
+        assert_eq!(
+            created,
+            Ok(BTreeMap::from([(1, 1), (2, 2), (3, 3), (4, 2)]))
+        );
+    }
+
+    #[test]
+    fn it_reports_ids_past_u32_max() {
+        let cards = get_cards("Card 4294967294: 1 2 | 1 2").unwrap();
+
+        assert_eq!(
+            find_derived_cards_with(cards.clone(), MissingCardPolicy::Create, MatchPolicy::Set),
+            Err(CardError::IdOverflow {
+                from: u32::MAX - 1,
+                offset: 2
+            })
+        );
+        assert_eq!(
+            find_derived_cards_with(cards, MissingCardPolicy::Clamp, MatchPolicy::Set),
+            Ok(BTreeMap::from([(u32::MAX - 1, 1)]))
+        );
+    }
+
+    #[test]
+    fn it_allows_gaps_and_any_order() {
+        // card 3 is missing, card 2 earns it and card 4 only under create
+        let input = "
+        Card 4: 9 | 8
+        Card 2: 1 2 | 1 2
+        Card 1: 1 | 1"
+            .trim();
+        let cards = get_cards(input).unwrap();
+
+        let created = find_derived_cards_with(cards, MissingCardPolicy::Create, MatchPolicy::Set);
+        assert_eq!(
+            created,
+            Ok(BTreeMap::from([(1, 1), (2, 2), (3, 2), (4, 3)]))
+        );
+    }
+
+    #[test]
+    fn it_matches_repeats_by_policy() {
+        let card = parse_line("Card 1: 5 5 7 9 | 5 5 5 7 7 8").unwrap();
+
+        assert_eq!(find_match_count_with(&card, MatchPolicy::Set), 2);
+        assert_eq!(find_match_count_with(&card, MatchPolicy::Multiset), 3);
+    }
+
+    #[test]
+    fn it_derives_cards_by_match_policy() {
+        // card 1 has one match as a set and two as a multiset
+        let input = "
+        Card 1: 5 5 | 5 5
+        Card 2: 1 | 2
+        Card 3: 1 | 2"
+            .trim();
+        let cards = get_cards(input).unwrap();
+
+        let set =
+            find_derived_cards_with(cards.clone(), MissingCardPolicy::Error, MatchPolicy::Set);
+        assert_eq!(set, Ok(BTreeMap::from([(1, 1), (2, 2), (3, 1)])));
+
+        let multiset =
+            find_derived_cards_with(cards, MissingCardPolicy::Error, MatchPolicy::Multiset);
+        assert_eq!(multiset, Ok(BTreeMap::from([(1, 1), (2, 2), (3, 2)])));
+    }
+}
@@ -0,0 +1,138 @@
+use rand::{
+    distributions::{Distribution, WeightedIndex},
+    rngs::StdRng,
+    seq::{index, SliceRandom},
+    SeedableRng,
+};
+
+use crate::cards::Card;
+
+/// The shape of a generated deck.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeckConfig {
+    pub count: u32,
+    pub winning_len: usize,
+    pub drawn_len: usize,
+    /// numbers are drawn from `min_number..=max_number`, which must fit both sides
+    pub min_number: u32,
+    pub max_number: u32,
+    /// relative weight of each match count, `match_weights[m]` for `m` matches
+    pub match_weights: Vec<u32>,
+}
+
+impl Default for DeckConfig {
+    /// Shaped like the puzzle input.
+    fn default() -> DeckConfig {
+        DeckConfig {
+            count: 200,
+            winning_len: 10,
+            drawn_len: 25,
+            min_number: 1,
+            max_number: 99,
+            match_weights: vec![40, 15, 10, 8, 6, 5, 4, 4, 3, 3, 2],
+        }
+    }
+}
+
+/// A random deck with unique numbers per side and ids `1..=count`; match counts
+/// follow `match_weights` but never earn a card past the end of the deck.
+///
+/// Panics if `min_number..=max_number` is too small for the requested sides.
+pub fn generate_deck(config: &DeckConfig, seed: u64) -> Vec<Card> {
+    let capacity = (u64::from(config.max_number) + 1).saturating_sub(config.min_number.into());
+    assert!(
+        capacity >= (config.winning_len + config.drawn_len) as u64,
+        "{}..={} cannot fit {} winning and {} drawn numbers",
+        config.min_number,
+        config.max_number,
+        config.winning_len,
+        config.drawn_len
+    );
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let weights = WeightedIndex::new(&config.match_weights).unwrap();
+
+    (1..=config.count)
+        .map(|id| {
+            let remaining = (config.count - id) as usize;
+            let matches = weights
+                .sample(&mut rng)
+                .min(config.winning_len)
+                .min(config.drawn_len)
+                .min(remaining);
+
+            // every number is distinct, the first `winning_len` are winning and
+            // the first `matches` of those are drawn again
+            let numbers = index::sample(
+                &mut rng,
+                capacity as usize,
+                config.winning_len + config.drawn_len - matches,
+            )
+            .into_iter()
+            .map(|i| config.min_number + i as u32)
+            .collect::<Vec<u32>>();
+
+            let winning = numbers[..config.winning_len].to_vec();
+            let mut drawn = winning[..matches].to_vec();
+            drawn.extend_from_slice(&numbers[config.winning_len..]);
+            drawn.shuffle(&mut rng);
+
+            Card { id, winning, drawn }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        cards::find_match_count,
+        generate::{generate_deck, DeckConfig},
+        validate::validate_cards,
+    };
+
+    #[test]
+    fn it_generates_a_valid_deck() {
+        let config = DeckConfig::default();
+        let deck = generate_deck(&config, 7);
+
+        assert_eq!(deck.len(), 200);
+        assert!(validate_cards(&deck).is_empty());
+
+        // the last card has nothing left to earn
+        assert_eq!(find_match_count(deck.last().unwrap()), 0);
+        assert!(deck.iter().all(|card| card.drawn.len() == 25));
+    }
+
+    #[test]
+    fn it_follows_the_seed_and_weights() {
+        let config = DeckConfig {
+            count: 50,
+            match_weights: vec![0, 0, 1],
+            ..Default::default()
+        };
+
+        assert_eq!(generate_deck(&config, 1), generate_deck(&config, 1));
+        assert_ne!(generate_deck(&config, 1), generate_deck(&config, 2));
+
+        let deck = generate_deck(&config, 1);
+        assert!(deck[..48].iter().all(|card| find_match_count(card) == 2));
+        assert_eq!(find_match_count(&deck[48]), 1);
+    }
+
+    #[test]
+    fn it_draws_numbers_from_the_configured_range() {
+        let config = DeckConfig {
+            min_number: 1000,
+            max_number: 1034,
+            ..Default::default()
+        };
+
+        let deck = generate_deck(&config, 3);
+
+        assert!(validate_cards(&deck).is_empty());
+        assert!(deck
+            .iter()
+            .flat_map(|card| card.winning.iter().chain(card.drawn.iter()))
+            .all(|n| (1000..=1034).contains(n)));
+    }
+}
@@ -0,0 +1,7 @@
+pub mod cards;
+pub mod generate;
+pub mod numbers;
+pub mod oracle;
+pub mod scoring;
+pub mod trace;
+pub mod validate;
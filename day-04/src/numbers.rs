@@ -0,0 +1,115 @@
+use std::collections::BTreeSet;
+
+use crate::cards::Card;
+
+/// A card's numbers as a set. Numbers below 128 fit in one `u128`, so
+/// intersecting two of them is an `and` plus a popcount; anything larger
+/// falls back to an ordered set.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum NumberSet {
+    Bits(u128),
+    General(BTreeSet<u32>),
+}
+
+impl NumberSet {
+    pub fn from_numbers(numbers: &[u32]) -> NumberSet {
+        if numbers.iter().all(|n| *n < 128) {
+            NumberSet::Bits(numbers.iter().fold(0, |bits, n| bits | (1 << n)))
+        } else {
+            NumberSet::General(numbers.iter().copied().collect())
+        }
+    }
+
+    pub fn len(&self) -> u32 {
+        match self {
+            NumberSet::Bits(bits) => bits.count_ones(),
+            NumberSet::General(set) => set.len() as u32,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn contains(&self, n: u32) -> bool {
+        match self {
+            NumberSet::Bits(bits) => n < 128 && bits & (1 << n) != 0,
+            NumberSet::General(set) => set.contains(&n),
+        }
+    }
+
+    /// How many numbers the two sets share.
+    pub fn intersection_len(&self, other: &NumberSet) -> u32 {
+        match (self, other) {
+            (NumberSet::Bits(a), NumberSet::Bits(b)) => (a & b).count_ones(),
+            (NumberSet::General(a), NumberSet::General(b)) => a.intersection(b).count() as u32,
+            (NumberSet::Bits(_), NumberSet::General(set)) => {
+                set.iter().filter(|n| self.contains(**n)).count() as u32
+            }
+            (NumberSet::General(set), NumberSet::Bits(_)) => {
+                set.iter().filter(|n| other.contains(**n)).count() as u32
+            }
+        }
+    }
+}
+
+/// A card with its numbers already packed, for counting matches many times.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PackedCard {
+    pub id: u32,
+    pub winning: NumberSet,
+    pub drawn: NumberSet,
+}
+
+impl PackedCard {
+    pub fn match_count(&self) -> u32 {
+        self.winning.intersection_len(&self.drawn)
+    }
+}
+
+impl From<&Card> for PackedCard {
+    fn from(card: &Card) -> PackedCard {
+        PackedCard {
+            id: card.id,
+            winning: NumberSet::from_numbers(&card.winning),
+            drawn: NumberSet::from_numbers(&card.drawn),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        cards::{find_match_count, parse_line},
+        numbers::{NumberSet, PackedCard},
+    };
+
+    #[test]
+    fn it_packs_small_numbers_into_bits() {
+        let set = NumberSet::from_numbers(&[0, 5, 127, 5]);
+
+        assert_eq!(set, NumberSet::Bits(1 | (1 << 5) | (1 << 127)));
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(127));
+        assert!(!set.contains(128));
+    }
+
+    #[test]
+    fn it_falls_back_for_large_numbers() {
+        let small = NumberSet::from_numbers(&[1, 2, 3, 100]);
+        let large = NumberSet::from_numbers(&[2, 3, 100, 500]);
+
+        assert!(matches!(large, NumberSet::General(_)));
+        assert_eq!(small.intersection_len(&large), 3);
+        assert_eq!(large.intersection_len(&small), 3);
+        assert_eq!(large.intersection_len(&large), 4);
+    }
+
+    #[test]
+    fn it_counts_the_same_matches_as_the_card() {
+        let card = parse_line("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53").unwrap();
+
+        assert_eq!(PackedCard::from(&card).match_count(), 4);
+        assert_eq!(find_match_count(&card), 4);
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::cards::{find_match_count, Card};
+
+/// The puzzle taken literally: every copy goes through a queue and hands out
+/// its own copies one at a time. Exponentially slow, only for checking
+/// `find_derived_cards` on small decks.
+///
+/// Cards earned past the end of the deck are skipped.
+pub fn simulate_copies(cards: &[Card]) -> BTreeMap<u32, u64> {
+    let by_id = cards
+        .iter()
+        .map(|card| (card.id, find_match_count(card)))
+        .collect::<BTreeMap<u32, u32>>();
+
+    let mut map: BTreeMap<u32, u64> = BTreeMap::new();
+    let mut queue: VecDeque<u32> = cards.iter().map(|card| card.id).collect();
+
+    while let Some(id) = queue.pop_front() {
+        *map.entry(id).or_insert(0) += 1;
+
+        for earned in (id + 1)..=(id + by_id[&id]) {
+            if by_id.contains_key(&earned) {
+                queue.push_back(earned);
+            }
+        }
+    }
+
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use proptest::prelude::*;
+
+    use crate::{
+        cards::{
+            find_derived_cards, find_derived_cards_with, get_cards, MatchPolicy, MissingCardPolicy,
+        },
+        generate::{generate_deck, DeckConfig},
+        oracle::simulate_copies,
+    };
+
+    #[test]
+    fn it_simulates_the_example() {
+        let input = "
+        Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+        Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+        Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+        Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+        Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+        Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"
+            .trim();
+
+        let actual = simulate_copies(&get_cards(input).unwrap());
+        let expected = BTreeMap::from([(1, 1), (2, 2), (3, 4), (4, 8), (5, 14), (6, 1)]);
+
+        assert_eq!(actual, expected);
+    }
+
+    // small decks with few matches, so the queue stays in the thousands
+    fn small_config() -> impl Strategy<Value = DeckConfig> {
+        (
+            1_u32..16,
+            1_usize..6,
+            1_usize..8,
+            prop::collection::vec(0_u32..5, 4),
+        )
+            .prop_filter("weights need a non-zero entry", |(_, _, _, weights)| {
+                weights.iter().any(|w| *w > 0)
+            })
+            .prop_map(
+                |(count, winning_len, drawn_len, match_weights)| DeckConfig {
+                    count,
+                    winning_len,
+                    drawn_len,
+                    min_number: 1,
+                    max_number: 20,
+                    match_weights,
+                },
+            )
+    }
+
+    proptest! {
+        #[test]
+        fn it_agrees_with_find_derived_cards(config in small_config(), seed in any::<u64>()) {
+            let deck = generate_deck(&config, seed);
+
+            let expected = simulate_copies(&deck);
+            let actual = find_derived_cards(deck).unwrap();
+
+            prop_assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn it_agrees_when_clamping_shuffled_decks(
+            config in small_config(),
+            seed in any::<u64>(),
+            drop in 0_usize..16,
+        ) {
+            // a gap in the ids and cards out of order
+            let mut deck = generate_deck(&config, seed);
+            if drop < deck.len() {
+                deck.remove(drop);
+            }
+            deck.reverse();
+
+            let expected = simulate_copies(&deck);
+            let actual = find_derived_cards_with(deck, MissingCardPolicy::Clamp, MatchPolicy::Set).unwrap();
+
+            prop_assert_eq!(actual, expected);
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use std::{fmt, str::FromStr};
+
+use crate::cards::{find_match_count_with, Card, MatchPolicy};
+
+/// How a card's match count turns into points. Scores saturate at `u32::MAX`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum ScoringRule {
+    /// 1 point for the first match, doubled for every match after it
+    #[default]
+    Doubling,
+    /// 1 point per match
+    Linear,
+    /// 1, 2, 3, 5, 8, ... each match adds the previous two scores
+    Fibonacci,
+    /// the same number of points for every match
+    FixedPerMatch(u32),
+    /// points looked up by match count, counts past the end use the last entry
+    Table(Vec<u32>),
+}
+
+impl ScoringRule {
+    pub fn score(&self, matches: u32) -> u32 {
+        if matches == 0 {
+            // only a table can award points for no matches
+            return match self {
+                ScoringRule::Table(table) => table.first().copied().unwrap_or(0),
+                _ => 0,
+            };
+        }
+
+        match self {
+            ScoringRule::Doubling => 2_u32.saturating_pow(matches - 1),
+            ScoringRule::Linear => matches,
+            ScoringRule::Fibonacci => {
+                let (mut a, mut b) = (1_u32, 1_u32);
+                for _ in 0..matches {
+                    (a, b) = (b, a.saturating_add(b));
+                }
+                a
+            }
+            ScoringRule::FixedPerMatch(points) => points.saturating_mul(matches),
+            ScoringRule::Table(table) => table
+                .get(matches as usize)
+                .or(table.last())
+                .copied()
+                .unwrap_or(0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownScoringRule(pub String);
+
+impl fmt::Display for UnknownScoringRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown scoring rule {:?}, expected doubling, linear, fibonacci, fixed:N or table:N,N,...",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for UnknownScoringRule {}
+
+/// Reads `doubling`, `linear`, `fibonacci`, `fixed:5` or `table:0,1,3,6`.
+impl FromStr for ScoringRule {
+    type Err = UnknownScoringRule;
+
+    fn from_str(s: &str) -> Result<ScoringRule, UnknownScoringRule> {
+        let unknown = || UnknownScoringRule(s.to_string());
+
+        let (name, arg) = match s.split_once(':') {
+            Some((name, arg)) => (name, Some(arg)),
+            None => (s, None),
+        };
+
+        match (name.trim(), arg) {
+            ("doubling", None) => Ok(ScoringRule::Doubling),
+            ("linear", None) => Ok(ScoringRule::Linear),
+            ("fibonacci", None) => Ok(ScoringRule::Fibonacci),
+            ("fixed", Some(points)) => points
+                .trim()
+                .parse::<u32>()
+                .map(ScoringRule::FixedPerMatch)
+                .map_err(|_| unknown()),
+            ("table", Some(entries)) => entries
+                .split(',')
+                .map(|f| f.trim().parse::<u32>())
+                .collect::<Result<Vec<u32>, _>>()
+                .map(ScoringRule::Table)
+                .map_err(|_| unknown()),
+            _ => Err(unknown()),
+        }
+    }
+}
+
+/// Each card's score under `rule`, ready for `find_signature`.
+pub fn find_scores(cards: &[Card], rule: &ScoringRule) -> Vec<u32> {
+    find_scores_with(cards, rule, MatchPolicy::Set)
+}
+
+pub fn find_scores_with(cards: &[Card], rule: &ScoringRule, policy: MatchPolicy) -> Vec<u32> {
+    cards
+        .iter()
+        .map(|card| rule.score(find_match_count_with(card, policy)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        cards::{find_signature, get_cards},
+        scoring::{find_scores, ScoringRule, UnknownScoringRule},
+    };
+
+    const EXAMPLE: &str = "
+    Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+    Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+    Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+    Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+    Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+    Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    #[test]
+    fn it_scores_each_rule() {
+        let counts = [0, 1, 2, 3, 4, 5];
+        let scores = |rule: ScoringRule| counts.map(|n| rule.score(n));
+
+        assert_eq!(scores(ScoringRule::Doubling), [0, 1, 2, 4, 8, 16]);
+        assert_eq!(scores(ScoringRule::Linear), [0, 1, 2, 3, 4, 5]);
+        assert_eq!(scores(ScoringRule::Fibonacci), [0, 1, 2, 3, 5, 8]);
+        assert_eq!(scores(ScoringRule::FixedPerMatch(3)), [0, 3, 6, 9, 12, 15]);
+        assert_eq!(
+            scores(ScoringRule::Table(vec![1, 10, 20])),
+            [1, 10, 20, 20, 20, 20]
+        );
+        assert_eq!(ScoringRule::Doubling.score(40), u32::MAX);
+    }
+
+    #[test]
+    fn it_signs_over_the_chosen_rule() {
+        // the example cards have 4, 2, 2, 1, 0 and 0 matches
+        let cards = get_cards(EXAMPLE.trim()).unwrap();
+
+        let signature = |rule: &ScoringRule| find_signature(find_scores(&cards, rule));
+
+        assert_eq!(signature(&ScoringRule::Doubling), 13);
+        assert_eq!(signature(&ScoringRule::Linear), 9);
+        assert_eq!(signature(&ScoringRule::Fibonacci), 5 + 2 + 2 + 1);
+        assert_eq!(signature(&ScoringRule::FixedPerMatch(10)), 90);
+        assert_eq!(signature(&ScoringRule::FixedPerMatch(u32::MAX)), u32::MAX);
+    }
+
+    #[test]
+    fn it_parses_rule_names() {
+        assert_eq!("linear".parse(), Ok(ScoringRule::Linear));
+        assert_eq!("fixed:5".parse(), Ok(ScoringRule::FixedPerMatch(5)));
+        assert_eq!(
+            "table:0, 1, 3".parse(),
+            Ok(ScoringRule::Table(vec![0, 1, 3]))
+        );
+        assert_eq!(
+            "fixed".parse::<ScoringRule>(),
+            Err(UnknownScoringRule("fixed".to_string()))
+        );
+        assert!("table:1,x".parse::<ScoringRule>().is_err());
+    }
+}
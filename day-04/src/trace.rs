@@ -0,0 +1,214 @@
+use std::collections::{BTreeMap, HashSet};
+
+use serde::Serialize;
+
+use crate::cards::{propagate, Card, CardError, MatchPolicy, MissingCardPolicy};
+
+/// Where one card's copies came from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CardTrace {
+    pub id: u32,
+    // 1 for cards in the deck, 0 for cards created by `MissingCardPolicy::Create`
+    pub original: u64,
+    // copies earned from each source card, keyed by the source's id
+    pub earned_from: BTreeMap<u32, u64>,
+    pub total: u64,
+}
+
+/// A record of every copy `find_derived_cards` hands out, so a card's
+/// total can be explained source by source.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CopyTrace {
+    pub cards: Vec<CardTrace>,
+}
+
+pub fn trace_derived_cards(
+    cards: Vec<Card>,
+    policy: MissingCardPolicy,
+    match_policy: MatchPolicy,
+) -> Result<CopyTrace, CardError> {
+    let originals = cards.iter().map(|card| card.id).collect::<HashSet<u32>>();
+
+    let mut earned: BTreeMap<u32, BTreeMap<u32, u64>> = BTreeMap::new();
+    let map = propagate(cards, policy, match_policy, |from, to, copies| {
+        earned.entry(to).or_default().insert(from, copies);
+    })?;
+
+    let cards = map
+        .into_iter()
+        .map(|(id, total)| CardTrace {
+            id,
+            original: if originals.contains(&id) { 1 } else { 0 },
+            earned_from: earned.remove(&id).unwrap_or_default(),
+            total,
+        })
+        .collect();
+
+    Ok(CopyTrace { cards })
+}
+
+impl CopyTrace {
+    pub fn card(&self, id: u32) -> Option<&CardTrace> {
+        self.cards.iter().find(|card| card.id == id)
+    }
+
+    /// One row per card, e.g. `5 | 14 | 1 | 1 from 1, 4 from 3, 8 from 4`.
+    pub fn to_table(&self) -> String {
+        let rows = self
+            .cards
+            .iter()
+            .map(|card| {
+                let sources = card
+                    .earned_from
+                    .iter()
+                    .map(|(from, copies)| format!("{} from {}", copies, from))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                [
+                    card.id.to_string(),
+                    card.total.to_string(),
+                    card.original.to_string(),
+                    sources,
+                ]
+            })
+            .collect::<Vec<[String; 4]>>();
+
+        let header = [
+            "card".to_string(),
+            "total".to_string(),
+            "original".to_string(),
+            "earned".to_string(),
+        ];
+        let widths = (0..3)
+            .map(|i| {
+                rows.iter()
+                    .chain([&header])
+                    .map(|row| row[i].len())
+                    .max()
+                    .unwrap()
+            })
+            .collect::<Vec<usize>>();
+
+        let mut out = String::new();
+        for row in [&header].into_iter().chain(rows.iter()) {
+            let line = format!(
+                "{:>w0$} | {:>w1$} | {:>w2$} | {}",
+                row[0],
+                row[1],
+                row[2],
+                row[3],
+                w0 = widths[0],
+                w1 = widths[1],
+                w2 = widths[2],
+            );
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+
+        out
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    /// Graphviz output, an edge per source card labelled with the copies it gave.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph cards {\n");
+
+        for card in self.cards.iter() {
+            out.push_str(&format!(
+                "    c{} [label=\"card {}\\n{} copies\"];\n",
+                card.id, card.id, card.total
+            ));
+        }
+
+        for card in self.cards.iter() {
+            for (from, copies) in card.earned_from.iter() {
+                out.push_str(&format!(
+                    "    c{} -> c{} [label=\"{}\"];\n",
+                    from, card.id, copies
+                ));
+            }
+        }
+
+        out.push_str("}\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::{
+        cards::{get_cards, MatchPolicy, MissingCardPolicy},
+        trace::trace_derived_cards,
+    };
+
+    const EXAMPLE: &str = "
+    Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+    Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+    Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+    Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+    Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+    Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    #[test]
+    fn it_explains_card_five() {
+        let cards = get_cards(EXAMPLE.trim()).unwrap();
+        let trace = trace_derived_cards(cards, MissingCardPolicy::Error, MatchPolicy::Set).unwrap();
+
+        let five = trace.card(5).unwrap();
+
+        assert_eq!(five.total, 14);
+        assert_eq!(five.original, 1);
+        assert_eq!(five.earned_from, BTreeMap::from([(1, 1), (3, 4), (4, 8)]));
+
+        // every card's sources add up to its total
+        for card in trace.cards.iter() {
+            assert_eq!(
+                card.original + card.earned_from.values().sum::<u64>(),
+                card.total
+            );
+        }
+    }
+
+    #[test]
+    fn it_renders_a_table() {
+        let cards = get_cards(EXAMPLE.trim()).unwrap();
+        let trace = trace_derived_cards(cards, MissingCardPolicy::Error, MatchPolicy::Set).unwrap();
+
+        let table = trace.to_table();
+        let lines = table.lines().collect::<Vec<&str>>();
+
+        assert_eq!(lines[0], "card | total | original | earned");
+        assert_eq!(lines[1], "   1 |     1 |        1 |");
+        assert_eq!(
+            lines[5],
+            "   5 |    14 |        1 | 1 from 1, 4 from 3, 8 from 4"
+        );
+    }
+
+    #[test]
+    fn it_exports_dot_and_json() {
+        let input = "
+        Card 1: 1 | 1
+        Card 2: 5 | 6"
+            .trim();
+        let cards = get_cards(input).unwrap();
+        let trace = trace_derived_cards(cards, MissingCardPolicy::Error, MatchPolicy::Set).unwrap();
+
+        let expected_dot = "digraph cards {
+    c1 [label=\"card 1\\n1 copies\"];
+    c2 [label=\"card 2\\n2 copies\"];
+    c1 -> c2 [label=\"1\"];
+}
+";
+        assert_eq!(trace.to_dot(), expected_dot);
+
+        let json: serde_json::Value = serde_json::from_str(&trace.to_json()).unwrap();
+        assert_eq!(json["cards"][1]["total"], 2);
+        assert_eq!(json["cards"][1]["earned_from"]["1"], 1);
+    }
+}
@@ -0,0 +1,178 @@
+use std::{collections::BTreeMap, fmt};
+
+use crate::cards::Card;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Winning,
+    Drawn,
+}
+
+impl fmt::Display for Side {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Side::Winning => write!(f, "winning"),
+            Side::Drawn => write!(f, "drawn"),
+        }
+    }
+}
+
+/// Something suspicious about a deck; none of these stop the puzzle from running.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    // `number` appears `count` times on one side of `card`
+    DuplicateNumber {
+        card: u32,
+        side: Side,
+        number: u32,
+        count: usize,
+    },
+    // a card whose columns differ from the first card's
+    InconsistentColumns {
+        card: u32,
+        side: Side,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::DuplicateNumber {
+                card,
+                side,
+                number,
+                count,
+            } => write!(
+                f,
+                "card {}: {} appears {} times in the {} numbers",
+                card, number, count, side
+            ),
+            Issue::InconsistentColumns {
+                card,
+                side,
+                expected,
+                found,
+            } => write!(
+                f,
+                "card {}: {} {} numbers, expected {}",
+                card, found, side, expected
+            ),
+        }
+    }
+}
+
+fn find_duplicates(card: u32, side: Side, numbers: &[u32]) -> Vec<Issue> {
+    let mut counts: BTreeMap<u32, usize> = BTreeMap::new();
+    for n in numbers.iter() {
+        *counts.entry(*n).or_insert(0) += 1;
+    }
+
+    counts
+        .into_iter()
+        .filter(|(_, count)| *count > 1)
+        .map(|(number, count)| Issue::DuplicateNumber {
+            card,
+            side,
+            number,
+            count,
+        })
+        .collect()
+}
+
+/// Repeated numbers and column counts that differ from the first card, in card order.
+pub fn validate_cards(cards: &[Card]) -> Vec<Issue> {
+    let Some(first) = cards.first() else {
+        return vec![];
+    };
+    let expected = [
+        (Side::Winning, first.winning.len()),
+        (Side::Drawn, first.drawn.len()),
+    ];
+
+    let mut issues: Vec<Issue> = vec![];
+
+    for card in cards.iter() {
+        for (side, numbers) in [(Side::Winning, &card.winning), (Side::Drawn, &card.drawn)] {
+            issues.extend(find_duplicates(card.id, side, numbers));
+        }
+
+        for ((side, expected), found) in expected.iter().zip([card.winning.len(), card.drawn.len()])
+        {
+            if found != *expected {
+                issues.push(Issue::InconsistentColumns {
+                    card: card.id,
+                    side: *side,
+                    expected: *expected,
+                    found,
+                });
+            }
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        cards::get_cards,
+        validate::{validate_cards, Issue, Side},
+    };
+
+    #[test]
+    fn it_accepts_a_clean_deck() {
+        let input = "
+        Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+        Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19"
+            .trim();
+
+        assert!(validate_cards(&get_cards(input).unwrap()).is_empty());
+    }
+
+    #[test]
+    fn it_reports_duplicates_and_columns() {
+        let input = "
+        Card 1: 1 2 3 | 4 5 6 7
+        Card 2: 1 1 3 | 4 5 6 6 6
+        Card 3: 1 2 | 4 5 6 7"
+            .trim();
+
+        let issues = validate_cards(&get_cards(input).unwrap());
+
+        assert_eq!(
+            issues,
+            vec![
+                Issue::DuplicateNumber {
+                    card: 2,
+                    side: Side::Winning,
+                    number: 1,
+                    count: 2,
+                },
+                Issue::DuplicateNumber {
+                    card: 2,
+                    side: Side::Drawn,
+                    number: 6,
+                    count: 3,
+                },
+                Issue::InconsistentColumns {
+                    card: 2,
+                    side: Side::Drawn,
+                    expected: 4,
+                    found: 5,
+                },
+                Issue::InconsistentColumns {
+                    card: 3,
+                    side: Side::Winning,
+                    expected: 3,
+                    found: 2,
+                },
+            ]
+        );
+        assert_eq!(
+            issues[1].to_string(),
+            "card 2: 6 appears 3 times in the drawn numbers"
+        );
+    }
+}
@@ -0,0 +1,369 @@
+use rayon::prelude::*;
+use std::{collections::HashMap, fmt, ops::Range};
+
+use crate::categories::{find_stages, CategoryError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Map {
+    // 50 98 2
+    pub destination_range_start: u64,
+    pub source_range_start: u64,
+    pub range_length: u64,
+}
+
+impl Map {
+    // ends past `u64::MAX` are cut short there, `validate_almanac` reports them
+    pub fn source_end(&self) -> u64 {
+        self.source_range_start.saturating_add(self.range_length)
+    }
+
+    pub fn contains(&self, val: u64) -> bool {
+        // val should equal or greater than source,
+        // val should be less than source + range; but NOT less than
+        // off by one haunts us all
+        val >= self.source_range_start && val < self.source_end()
+    }
+
+    pub fn apply(&self, val: u64) -> u64 {
+        self.destination_range_start
+            .saturating_add(val - self.source_range_start)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Almanac {
+    // a single seed is a range of one
+    pub seeds: Vec<Range<u64>>,
+
+    pub maps: HashMap<String, Vec<Map>>,
+}
+
+impl Almanac {
+    // a u128, since the ranges' lengths can add up past u64::MAX
+    pub fn seed_count(&self) -> u128 {
+        self.seeds
+            .iter()
+            .map(|range| u128::from(range.end - range.start))
+            .sum()
+    }
+
+    /// Every seed in order, produced one at a time.
+    pub fn seed_values(&self) -> impl Iterator<Item = u64> + '_ {
+        self.seeds.iter().flat_map(|range| range.clone())
+    }
+
+    /// Every seed, split across rayon's threads by range and within each range.
+    pub fn par_seed_values(&self) -> impl ParallelIterator<Item = u64> + '_ {
+        self.seeds
+            .par_iter()
+            .flat_map(|range| range.clone().into_par_iter())
+    }
+
+    /// Every `step`th seed of each range, starting at the range's first seed.
+    pub fn par_sample_seeds(&self, step: u64) -> impl ParallelIterator<Item = u64> + '_ {
+        assert!(step > 0, "step must be at least 1");
+
+        self.seeds.par_iter().flat_map(move |range| {
+            let start = range.start;
+            (0..(range.end - range.start).div_ceil(step))
+                .into_par_iter()
+                .map(move |i| start + i * step)
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlmanacError {
+    ExpectedSeeds(String),
+    InvalidNumber(String),
+    // `start length` pairs were asked for but the seeds line has this many values
+    OddSeedPairs(usize),
+    SeedRangeOverflow { start: u64, length: u64 },
+    // a map line without exactly three numbers
+    ExpectedMap(String),
+    DuplicateMap(String),
+    UnexpectedLine(String),
+}
+
+impl fmt::Display for AlmanacError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlmanacError::ExpectedSeeds(line) => write!(f, "expected \"seeds:\" in {:?}", line),
+            AlmanacError::InvalidNumber(n) => write!(f, "{:?} is not a number", n),
+            AlmanacError::OddSeedPairs(count) => {
+                write!(f, "seed ranges come in pairs, found {} values", count)
+            }
+            AlmanacError::SeedRangeOverflow { start, length } => {
+                write!(f, "seed range {} {} runs past u64::MAX", start, length)
+            }
+            AlmanacError::ExpectedMap(line) => {
+                write!(f, "expected \"destination source length\" in {:?}", line)
+            }
+            AlmanacError::DuplicateMap(name) => write!(f, "map {:?} appears more than once", name),
+            AlmanacError::UnexpectedLine(line) => write!(f, "unexpected line {:?}", line),
+        }
+    }
+}
+
+impl std::error::Error for AlmanacError {}
+
+/// How the numbers on the seeds line are read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SeedMode {
+    /// every number is one seed, as in part1
+    #[default]
+    Values,
+    /// numbers are `start length` pairs, as in part2
+    Ranges,
+}
+
+fn parse_numbers(input: &str) -> Result<Vec<u64>, AlmanacError> {
+    input
+        .split_whitespace()
+        .map(|f| {
+            f.parse::<u64>()
+                .map_err(|_| AlmanacError::InvalidNumber(f.to_string()))
+        })
+        .collect()
+}
+
+fn parse_seeds(line: &str, mode: SeedMode) -> Result<Vec<Range<u64>>, AlmanacError> {
+    let values = parse_numbers(
+        line.strip_prefix("seeds:")
+            .ok_or_else(|| AlmanacError::ExpectedSeeds(line.to_string()))?,
+    )?;
+
+    match mode {
+        SeedMode::Values => values
+            .into_iter()
+            .map(|seed| {
+                seed.checked_add(1)
+                    .map(|end| seed..end)
+                    .ok_or(AlmanacError::SeedRangeOverflow {
+                        start: seed,
+                        length: 1,
+                    })
+            })
+            .collect(),
+        SeedMode::Ranges => {
+            if !values.len().is_multiple_of(2) {
+                return Err(AlmanacError::OddSeedPairs(values.len()));
+            }
+            values
+                .chunks_exact(2)
+                .map(|pair| {
+                    let (start, length) = (pair[0], pair[1]);
+                    start
+                        .checked_add(length)
+                        .map(|end| start..end)
+                        .ok_or(AlmanacError::SeedRangeOverflow { start, length })
+                })
+                .collect()
+        }
+    }
+}
+
+fn parse_map(line: &str) -> Result<Map, AlmanacError> {
+    match parse_numbers(line)?[..] {
+        [destination_range_start, source_range_start, range_length] => Ok(Map {
+            destination_range_start,
+            source_range_start,
+            range_length,
+        }),
+        _ => Err(AlmanacError::ExpectedMap(line.to_string())),
+    }
+}
+
+/// Reads every number on the seeds line as one seed, as part1 does.
+pub fn parse_almanac(input: &str) -> Result<Almanac, AlmanacError> {
+    parse_almanac_with(input, SeedMode::Values)
+}
+
+/// The seeds line first, then blocks of a `name map:` header and its maps,
+/// separated by blank lines.
+pub fn parse_almanac_with(input: &str, mode: SeedMode) -> Result<Almanac, AlmanacError> {
+    let lines = input.split('\n').map(|f| f.trim()).collect::<Vec<&str>>();
+    let seeds = parse_seeds(lines.first().copied().unwrap_or_default(), mode)?;
+
+    let mut hash_map: HashMap<String, Vec<Map>> = HashMap::new();
+
+    let mut i = 1;
+    while i < lines.len() {
+        let line = lines[i];
+        if line.is_empty() {
+            i += 1;
+            continue;
+        }
+
+        let Some(name) = line.strip_suffix("map:") else {
+            return Err(AlmanacError::UnexpectedLine(line.to_string()));
+        };
+        let name = name.trim().to_string();
+        let mut map_list: Vec<Map> = vec![];
+
+        loop {
+            i += 1;
+
+            let Some(forward_line) = lines.get(i) else {
+                break;
+            };
+
+            if forward_line.is_empty() {
+                break;
+            }
+
+            map_list.push(parse_map(forward_line)?);
+        }
+
+        if hash_map.contains_key(&name) {
+            return Err(AlmanacError::DuplicateMap(name));
+        }
+        hash_map.insert(name, map_list);
+
+        i += 1;
+    }
+
+    Ok(Almanac {
+        seeds,
+        maps: hash_map,
+    })
+}
+
+/// Follows `val` through every map from category `from` to category `to`.
+/// The chain is looked up on every call; to trace many values, look it up
+/// once with `find_stages` and use `trace_stages`.
+pub fn find_trace(almanac: &Almanac, from: &str, to: &str, val: u64) -> Result<u64, CategoryError> {
+    // seed to soil
+    // source to destination
+
+    Ok(trace_stages(&find_stages(almanac, from, to)?, val))
+}
+
+/// Follows `val` through `stages` in order, see `find_stages`.
+pub fn trace_stages(stages: &[&[Map]], val: u64) -> u64 {
+    let mut val = val;
+    for maps in stages.iter() {
+        // stop trying maps from the same category if it is already found
+        if let Some(map) = maps.iter().find(|map| map.contains(val)) {
+            val = map.apply(val);
+        }
+    }
+
+    val
+}
+
+pub fn find_trace_seed_to_location(almanac: &Almanac, seed: u64) -> Result<u64, CategoryError> {
+    find_trace(almanac, "seed", "location", seed)
+}
+
+// the puzzle's example, shared by the tests of every module
+#[cfg(test)]
+pub(crate) const EXAMPLE: &str = "
+    seeds: 79 14 55 13
+
+    seed-to-soil map:
+    50 98 2
+    52 50 48
+
+    soil-to-fertilizer map:
+    0 15 37
+    37 52 2
+    39 0 15
+
+    fertilizer-to-water map:
+    49 53 8
+    0 11 42
+    42 0 7
+    57 7 4
+
+    water-to-light map:
+    88 18 7
+    18 25 70
+
+    light-to-temperature map:
+    45 77 23
+    81 45 19
+    68 64 13
+
+    temperature-to-humidity map:
+    0 69 1
+    1 0 69
+
+    humidity-to-location map:
+    60 56 37
+    56 93 4";
+
+#[cfg(test)]
+mod tests {
+    use crate::almanac::{parse_almanac, parse_almanac_with, AlmanacError, Map, SeedMode};
+
+    const INPUT: &str = "
+    seeds: 79 14 55 13
+
+    seed-to-soil map:
+    50 98 2
+    52 50 48";
+
+    #[test]
+    fn it_reads_seeds_by_mode() {
+        let values = parse_almanac(INPUT.trim()).unwrap();
+        let ranges = parse_almanac_with(INPUT.trim(), SeedMode::Ranges).unwrap();
+
+        assert_eq!(values.seeds, vec![79..80, 14..15, 55..56, 13..14]);
+        assert_eq!(ranges.seeds, vec![79..93, 55..68]);
+        assert_eq!(values.maps, ranges.maps);
+        assert_eq!((values.seed_count(), ranges.seed_count()), (4, 27));
+
+        let wide = parse_almanac_with("seeds: 0 18446744073709551614 5 10", SeedMode::Ranges);
+        assert_eq!(wide.unwrap().seed_count(), u128::from(u64::MAX) + 9);
+        assert_eq!(
+            values.maps["seed-to-soil"][1],
+            Map {
+                destination_range_start: 52,
+                source_range_start: 50,
+                range_length: 48,
+            }
+        );
+    }
+
+    #[test]
+    fn it_rejects_malformed_almanacs() {
+        let ranges = |input: &str| parse_almanac_with(input, SeedMode::Ranges).map(|_| ());
+
+        assert_eq!(ranges("seeds: 1 2 3"), Err(AlmanacError::OddSeedPairs(3)));
+        assert_eq!(
+            ranges("seeds: 18446744073709551615 1"),
+            Err(AlmanacError::SeedRangeOverflow {
+                start: u64::MAX,
+                length: 1
+            })
+        );
+        assert_eq!(
+            parse_almanac("seeds: 18446744073709551615").map(|_| ()),
+            Err(AlmanacError::SeedRangeOverflow {
+                start: u64::MAX,
+                length: 1
+            })
+        );
+        assert_eq!(
+            parse_almanac("seed: 1").map(|_| ()),
+            Err(AlmanacError::ExpectedSeeds("seed: 1".to_string()))
+        );
+        assert_eq!(
+            parse_almanac("seeds: 1 x").map(|_| ()),
+            Err(AlmanacError::InvalidNumber("x".to_string()))
+        );
+        assert_eq!(
+            parse_almanac("seeds: 1\n\nseed-to-soil map:\n1 2").map(|_| ()),
+            Err(AlmanacError::ExpectedMap("1 2".to_string()))
+        );
+        assert_eq!(
+            parse_almanac("seeds: 1\n\n1 2 3").map(|_| ()),
+            Err(AlmanacError::UnexpectedLine("1 2 3".to_string()))
+        );
+        assert_eq!(
+            parse_almanac("seeds: 1\n\na-to-b map:\n1 2 3\n\na-to-b map:\n4 5 6").map(|_| ()),
+            Err(AlmanacError::DuplicateMap("a-to-b".to_string()))
+        );
+    }
+}
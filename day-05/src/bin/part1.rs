@@ -1,156 +1,75 @@
-use std::collections::HashMap;
-
+use day_05::{
+    almanac::{parse_almanac, trace_stages},
+    categories::find_stages,
+    report::{render_path, render_svg, render_tables},
+    validate::{check_almanac, ValidationMode},
+};
+
+// cargo run --bin part1 -- --strict
+// cargo run --bin part1 -- --report table|path|svg --seed 79
 fn main() {
     let input = include_str!("input1.txt");
 
-    let almanac = parse_almanac(input);
-
-    let result = almanac
-        .seeds
-        .clone()
-        .into_iter()
-        .map(|seed| find_trace_seed_to_location(&almanac, seed))
-        .collect::<Vec<u64>>();
-
-    println!("result = {:#?}", result);
-
-    let minimum = result.into_iter().min().unwrap();
-
-    println!("answer = {}", minimum);
-    // 3374647
-}
-
-#[derive(Debug)]
-struct Map {
-    // 50 98 2
-    destination_range_start: u64,
-    source_range_start: u64,
-    range_length: u64,
-}
-
-#[derive(Debug)]
-struct Almanac {
-    seeds: Vec<u64>,
-
-    maps: HashMap<String, Vec<Map>>,
-}
-
-fn parse_almanac(input: &str) -> Almanac {
-    let lines = input.split("\n").map(|f| f.trim()).collect::<Vec<&str>>();
-    let seeds_line = lines.first().unwrap().replace("seeds:", "");
-    let seeds = seeds_line
-        .trim()
-        .split(" ")
-        .map(|f| f.trim())
-        .map(|f| f.parse::<u64>().unwrap())
-        .collect::<Vec<u64>>();
-
-    let mut hash_map: HashMap<String, Vec<Map>> = HashMap::new();
-
-    let mut i = 0;
-    loop {
-        let line = lines[i];
-        if line.contains("map") {
-            let name = line.replace("map:", "").trim().to_string();
-            let mut map_list: Vec<Map> = vec![];
-
-            loop {
-                i = i + 1;
-                let forward_line_opt = lines.get(i);
-
-                if forward_line_opt.is_none() {
-                    break;
-                }
-
-                let forward_line = forward_line_opt.unwrap();
-
-                if forward_line.trim().is_empty() {
-                    break;
+    let almanac = parse_almanac(input).unwrap();
+
+    let args = std::env::args().collect::<Vec<String>>();
+    if let Some(i) = args.iter().position(|arg| arg == "--report") {
+        // the path and svg follow one seed if given, otherwise all of them
+        let seeds = match args.iter().position(|arg| arg == "--seed") {
+            Some(j) => {
+                let seed = args.get(j + 1).and_then(|f| f.parse::<u64>().ok());
+                match seed.and_then(|seed| seed.checked_add(1).map(|end| seed..end)) {
+                    Some(range) => std::iter::once(range).collect(),
+                    None => {
+                        eprintln!("error: --seed needs a seed below u64::MAX, e.g. --seed 79");
+                        std::process::exit(1);
+                    }
                 }
-
-                println!("forward line: {}", forward_line);
-
-                let parts = forward_line
-                    .split(" ")
-                    .map(|f| f.trim())
-                    .map(|f| f.parse::<u64>().unwrap())
-                    .collect::<Vec<u64>>();
-
-                let map = Map {
-                    destination_range_start: parts[0],
-                    source_range_start: parts[1],
-                    range_length: parts[2],
-                };
-
-                map_list.push(map);
             }
-
-            hash_map.insert(name, map_list);
-        }
-
-        i = i + 1;
-
-        if i > lines.len() {
-            break;
+            None => almanac.seeds.clone(),
+        };
+        match args.get(i + 1).map(|f| f.as_str()) {
+            Some("path") => print!("{}", render_path(&almanac, &seeds).unwrap()),
+            Some("svg") => print!("{}", render_svg(&almanac, &seeds).unwrap()),
+            _ => print!("{}", render_tables(&almanac).unwrap()),
         }
+        return;
     }
 
-    let almanac = Almanac {
-        seeds,
-        maps: hash_map,
+    // --strict refuses an almanac with overlaps, gaps or overflow
+    let mode = if args.iter().any(|arg| arg == "--strict") {
+        ValidationMode::Strict
+    } else {
+        ValidationMode::Lenient
     };
-
-    return almanac;
-}
-
-fn find_trace_seed_to_location(almanac: &Almanac, seed: u64) -> u64 {
-    let order = vec![
-        "seed-to-soil",
-        "soil-to-fertilizer",
-        "fertilizer-to-water",
-        "water-to-light",
-        "light-to-temperature",
-        "temperature-to-humidity",
-        "humidity-to-location",
-    ];
-
-    // seed to soil
-    // source to destination
-
-    let mut val = seed;
-    for ord in order.into_iter() {
-        let maps = almanac.maps.get(ord).unwrap();
-        for map in maps.into_iter() {
-            if val >= map.source_range_start && val <= map.source_range_start + map.range_length {
-                println!(
-                    "{}: {} >= {} && {} <= {}",
-                    ord,
-                    val,
-                    map.source_range_start,
-                    val,
-                    map.source_range_start + map.range_length
-                );
-                let offset = val - map.source_range_start;
-                let dest = map.destination_range_start + offset;
-
-                println!("{}: mapped source {} -> destination {}", ord, val, dest);
-                val = dest;
-                break; // stop trying maps from the same category
-                       // if it is already found
-            } else {
-                println!("{}: mapped source {} -> destination {}", ord, val, val);
-            }
+    let issues = match check_almanac(&almanac, mode) {
+        Ok(issues) => issues,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            std::process::exit(1);
         }
+    };
+    for issue in issues {
+        eprintln!("warning: {}", issue);
     }
 
-    println!("final val = {}", val);
+    let stages = find_stages(&almanac, "seed", "location").unwrap();
+    let result = almanac
+        .seed_values()
+        .map(|seed| trace_stages(&stages, seed))
+        .collect::<Vec<u64>>();
+
+    println!("result = {:#?}", result);
+
+    let minimum = result.into_iter().min().unwrap();
 
-    return val;
+    println!("answer = {}", minimum);
+    // 3374647
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{find_trace_seed_to_location, parse_almanac};
+    use day_05::almanac::{find_trace_seed_to_location, parse_almanac};
 
     #[test]
     fn it_blends() {
@@ -191,12 +110,12 @@ mod tests {
         "
         .trim();
 
-        let almanac = parse_almanac(input);
+        let almanac = parse_almanac(input).unwrap();
 
-        let seed_79_to_location = find_trace_seed_to_location(&almanac, 79);
-        let seed_14_to_location = find_trace_seed_to_location(&almanac, 14);
-        let seed_55_to_location = find_trace_seed_to_location(&almanac, 55);
-        let seed_13_to_location = find_trace_seed_to_location(&almanac, 13);
+        let seed_79_to_location = find_trace_seed_to_location(&almanac, 79).unwrap();
+        let seed_14_to_location = find_trace_seed_to_location(&almanac, 14).unwrap();
+        let seed_55_to_location = find_trace_seed_to_location(&almanac, 55).unwrap();
+        let seed_13_to_location = find_trace_seed_to_location(&almanac, 13).unwrap();
 
         assert_eq!(seed_79_to_location, 82);
         assert_eq!(seed_14_to_location, 43);
@@ -1,196 +1,75 @@
-use rayon::current_thread_index;
-use rayon::prelude::*;
-use std::collections::HashMap;
-
+use day_05::{
+    almanac::{parse_almanac_with, SeedMode},
+    brute::{find_lowest_location_brute_force, find_lowest_location_sampled},
+    inverse::find_lowest_location_by_inverse,
+    ranges::find_lowest_location,
+    validate::{check_almanac, ValidationMode},
+};
+
+// cargo run --bin part2 -- --inverse --strict
+// cargo run --release --bin part2 -- --sample 1000
 fn main() {
     let input = include_str!("input1.txt");
 
-    let almanac = parse_almanac(input);
-
-    println!("total seeds in the almanac = {}", almanac.seeds.len());
-
-    let seeds = &almanac.seeds;
-
-    let results = seeds
-        .clone()
-        .into_par_iter()
-        .map(|f| find_trace_seed_to_location(&almanac, f))
-        .collect::<Vec<u64>>();
-
-    let min = results.into_iter().min().unwrap();
-
-    println!("answer = {}", min);
-    // 6082852
-    // disclaimer
-    // this was brute forced - made time possible with rayon and space possible by m1 (fails on linux)
-}
-
-#[derive(Debug)]
-struct Map {
-    // 50 98 2
-    destination_range_start: u64,
-    source_range_start: u64,
-    range_length: u64,
-}
-
-#[derive(Debug)]
-struct Almanac {
-    seeds: Vec<u64>,
-
-    maps: HashMap<String, Vec<Map>>,
-}
-
-fn parse_almanac(input: &str) -> Almanac {
-    let lines = input.split("\n").map(|f| f.trim()).collect::<Vec<&str>>();
-    let seeds_line = lines.first().unwrap().replace("seeds:", "");
-
     // in part2, seeds are ranges, "position offset" pairs
-    // we could explode the size of this array but it is amusing
-
-    let seed_line_numbers = seeds_line
-        .trim()
-        .split(" ")
-        .map(|f| f.trim())
-        .map(|f| f.parse::<u64>().unwrap())
-        .collect::<Vec<u64>>();
-
-    let mut seeds: Vec<u64> = vec![];
-    let mut i = 0;
-    loop {
-        if i >= seed_line_numbers.len() {
-            break;
-        }
-
-        let position = seed_line_numbers[i];
-        let offset = seed_line_numbers[i + 1];
-
-        println!("seed group = {}", position);
-
-        for n in position..(position + offset) {
-            seeds.push(n);
-        }
-
-        i = i + 2;
-    }
-
-    println!("number of seeds = {}", seeds.len());
-
-    let mut hash_map: HashMap<String, Vec<Map>> = HashMap::new();
+    let almanac = parse_almanac_with(input, SeedMode::Ranges).unwrap();
 
-    let mut i = 0;
-    loop {
-        let line = lines[i];
-        if line.contains("map") {
-            let name = line.replace("map:", "").trim().to_string();
-            let mut map_list: Vec<Map> = vec![];
+    let args = std::env::args().collect::<Vec<String>>();
 
-            loop {
-                i = i + 1;
-                let forward_line_opt = lines.get(i);
-
-                if forward_line_opt.is_none() {
-                    break;
-                }
-
-                let forward_line = forward_line_opt.unwrap();
-
-                if forward_line.trim().is_empty() {
-                    break;
-                }
-
-                // println!("forward line: {}", forward_line);
-
-                let parts = forward_line
-                    .split(" ")
-                    .map(|f| f.trim())
-                    .map(|f| f.parse::<u64>().unwrap())
-                    .collect::<Vec<u64>>();
-
-                let map = Map {
-                    destination_range_start: parts[0],
-                    source_range_start: parts[1],
-                    range_length: parts[2],
-                };
-
-                map_list.push(map);
-            }
-
-            hash_map.insert(name, map_list);
-        }
-
-        i = i + 1;
-
-        if i > lines.len() {
-            break;
+    // --strict refuses an almanac with overlaps, gaps or overflow
+    let mode = if args.iter().any(|arg| arg == "--strict") {
+        ValidationMode::Strict
+    } else {
+        ValidationMode::Lenient
+    };
+    let issues = match check_almanac(&almanac, mode) {
+        Ok(issues) => issues,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            std::process::exit(1);
         }
-    }
-
-    let almanac = Almanac {
-        seeds,
-        maps: hash_map,
     };
+    for issue in issues {
+        eprintln!("warning: {}", issue);
+    }
 
-    return almanac;
-}
-
-fn find_trace_seed_to_location(almanac: &Almanac, seed: u64) -> u64 {
-    // println!(
-    //     "{}: seed = {}",
-    //     rayon::current_thread_index().unwrap(),
-    //     seed
-    // );
-
-    let order = vec![
-        "seed-to-soil",
-        "soil-to-fertilizer",
-        "fertilizer-to-water",
-        "water-to-light",
-        "light-to-temperature",
-        "temperature-to-humidity",
-        "humidity-to-location",
-    ];
-
-    // seed to soil
-    // source to destination
+    // kept as ranges and split at every map boundary instead of exploded
+    let ranges = &almanac.seeds;
 
-    let mut val = seed;
-    for ord in order.into_iter() {
-        let maps = almanac.maps.get(ord).unwrap();
-        for map in maps.into_iter() {
-            // val should equal or greater than source,
-            // val should be less than source + range; but NOT less than
-            // off by one haunts us all
-            if val >= map.source_range_start && val < map.source_range_start + map.range_length {
-                // println!(
-                //     "{}: {} >= {} && {} < {}",
-                //     ord,
-                //     val,
-                //     map.source_range_start,
-                //     val,
-                //     map.source_range_start + map.range_length
-                // );
-                let offset = val - map.source_range_start;
-                let dest = map.destination_range_start + offset;
+    println!("total seed ranges in the almanac = {}", ranges.len());
 
-                // println!("{}: mapped source {} -> destination {}", ord, val, dest);
-                val = dest;
-                break; // stop trying maps from the same category
-                       // if it is already found
-            } else {
-                // println!("{}: mapped source {} -> destination {}", ord, val, val);
+    // --inverse searches from the location side instead, --brute-force and
+    // --sample N trace single seeds straight from the ranges
+    let sample = args.iter().position(|arg| arg == "--sample").map(|i| {
+        match args.get(i + 1).and_then(|f| f.parse::<u64>().ok()) {
+            Some(step) if step > 0 => step,
+            _ => {
+                eprintln!("error: --sample needs a step of at least 1, e.g. --sample 1000");
+                std::process::exit(1);
             }
         }
+    });
+    let min = if args.iter().any(|arg| arg == "--inverse") {
+        find_lowest_location_by_inverse(&almanac, ranges).unwrap()
+    } else if args.iter().any(|arg| arg == "--brute-force") {
+        find_lowest_location_brute_force(&almanac).unwrap()
+    } else if let Some(step) = sample {
+        find_lowest_location_sampled(&almanac, step).unwrap()
+    } else {
+        find_lowest_location(&almanac, ranges).unwrap()
     }
+    .unwrap();
 
-    // println!("final val = {}", val);
-
-    return val;
+    println!("answer = {}", min);
+    // 6082852
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{find_trace_seed_to_location, parse_almanac};
-    use rayon::prelude::*;
+    use day_05::{
+        almanac::{parse_almanac_with, SeedMode},
+        ranges::find_lowest_location,
+    };
 
     #[test]
     fn it_blends_long() {
@@ -231,11 +110,11 @@ mod tests {
         "
         .trim();
 
-        let almanac = parse_almanac(input);
+        let almanac = parse_almanac_with(input, SeedMode::Ranges).unwrap();
 
-        let seeds = &almanac.seeds;
+        let seeds = almanac.seed_count();
 
-        assert_eq!(2761436232, seeds.len());
+        assert_eq!(2761436232, seeds);
     }
 
     #[test]
@@ -277,19 +156,11 @@ mod tests {
         "
         .trim();
 
-        let almanac = parse_almanac(input);
-
-        let seeds = &almanac.seeds;
-
-        println!("seeds = {:#?}", seeds);
-
-        let results = seeds
-            .clone()
-            .into_par_iter()
-            .map(|f| find_trace_seed_to_location(&almanac, f))
-            .collect::<Vec<u64>>();
+        let almanac = parse_almanac_with(input, SeedMode::Ranges).unwrap();
 
-        let min = results.into_iter().min().unwrap();
+        let min = find_lowest_location(&almanac, &almanac.seeds)
+            .unwrap()
+            .unwrap();
 
         let expected = 46;
 
@@ -0,0 +1,79 @@
+use rayon::prelude::*;
+
+use crate::{
+    almanac::{trace_stages, Almanac},
+    categories::{find_stages, CategoryError},
+};
+
+/// Traces every seed on its own, in parallel and without collecting them first.
+pub fn find_lowest_location_brute_force(almanac: &Almanac) -> Result<Option<u64>, CategoryError> {
+    let stages = find_stages(almanac, "seed", "location")?;
+
+    Ok(almanac
+        .par_seed_values()
+        .map(|seed| trace_stages(&stages, seed))
+        .min())
+}
+
+/// Like `find_lowest_location_brute_force` but only every `step`th seed of
+/// each range, so the result is an upper bound on the real lowest location.
+pub fn find_lowest_location_sampled(
+    almanac: &Almanac,
+    step: u64,
+) -> Result<Option<u64>, CategoryError> {
+    let stages = find_stages(almanac, "seed", "location")?;
+
+    Ok(almanac
+        .par_sample_seeds(step)
+        .map(|seed| trace_stages(&stages, seed))
+        .min())
+}
+
+#[cfg(test)]
+mod tests {
+    use rayon::prelude::*;
+
+    use crate::{
+        almanac::{parse_almanac_with, Almanac, SeedMode, EXAMPLE},
+        brute::{find_lowest_location_brute_force, find_lowest_location_sampled},
+        ranges::find_lowest_location,
+    };
+
+    #[test]
+    fn it_iterates_seeds_lazily() {
+        let almanac = Almanac {
+            seeds: vec![10..13, 0..2, 5..5],
+            maps: Default::default(),
+        };
+
+        assert_eq!(almanac.seed_count(), 5);
+        assert_eq!(
+            almanac.seed_values().collect::<Vec<u64>>(),
+            vec![10, 11, 12, 0, 1]
+        );
+
+        let mut parallel = almanac.par_seed_values().collect::<Vec<u64>>();
+        parallel.sort();
+        assert_eq!(parallel, vec![0, 1, 10, 11, 12]);
+
+        let mut sampled = almanac.par_sample_seeds(2).collect::<Vec<u64>>();
+        sampled.sort();
+        assert_eq!(sampled, vec![0, 10, 12]);
+    }
+
+    #[test]
+    fn it_brute_forces_the_lowest_location() {
+        let almanac = parse_almanac_with(EXAMPLE.trim(), SeedMode::Ranges).unwrap();
+
+        assert_eq!(find_lowest_location_brute_force(&almanac), Ok(Some(46)));
+        assert_eq!(
+            find_lowest_location_brute_force(&almanac),
+            find_lowest_location(&almanac, &almanac.seeds)
+        );
+
+        // sampling can only miss the lowest location, never undercut it
+        let sampled = find_lowest_location_sampled(&almanac, 5).unwrap().unwrap();
+        assert!(sampled >= 46);
+        assert_eq!(find_lowest_location_sampled(&almanac, 1), Ok(Some(46)));
+    }
+}
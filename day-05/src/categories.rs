@@ -0,0 +1,196 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    fmt,
+};
+
+use crate::almanac::{Almanac, Map};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CategoryError {
+    // a map name that is not "X-to-Y"
+    MalformedHeader(String),
+    UnknownCategory(String),
+    NoPath { from: String, to: String },
+}
+
+impl fmt::Display for CategoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CategoryError::MalformedHeader(name) => {
+                write!(f, "map {:?} is not named \"X-to-Y\"", name)
+            }
+            CategoryError::UnknownCategory(name) => {
+                write!(f, "no map reads or writes category {:?}", name)
+            }
+            CategoryError::NoPath { from, to } => {
+                write!(f, "no chain of maps leads from {:?} to {:?}", from, to)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CategoryError {}
+
+/// Splits a map name like `seed-to-soil` into its two categories.
+pub fn parse_header(name: &str) -> Result<(&str, &str), CategoryError> {
+    match name.split_once("-to-") {
+        Some((from, to)) if !from.is_empty() && !to.is_empty() => Ok((from, to)),
+        _ => Err(CategoryError::MalformedHeader(name.to_string())),
+    }
+}
+
+/// The categories of an almanac, with an edge for every map.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CategoryGraph {
+    edges: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl CategoryGraph {
+    pub fn from_almanac(almanac: &Almanac) -> Result<CategoryGraph, CategoryError> {
+        let mut graph = CategoryGraph::default();
+
+        for name in almanac.maps.keys() {
+            let (from, to) = parse_header(name)?;
+            graph
+                .edges
+                .entry(from.to_string())
+                .or_default()
+                .insert(to.to_string());
+            graph.edges.entry(to.to_string()).or_default();
+        }
+
+        Ok(graph)
+    }
+
+    pub fn categories(&self) -> impl Iterator<Item = &str> {
+        self.edges.keys().map(|f| f.as_str())
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.edges
+            .iter()
+            .flat_map(|(from, to)| to.iter().map(move |to| (from.as_str(), to.as_str())))
+    }
+
+    /// The fewest categories leading from `from` to `to`, both included.
+    pub fn path(&self, from: &str, to: &str) -> Result<Vec<String>, CategoryError> {
+        for category in [from, to] {
+            if !self.edges.contains_key(category) {
+                return Err(CategoryError::UnknownCategory(category.to_string()));
+            }
+        }
+
+        // breadth first, remembering where each category was reached from
+        let mut previous: BTreeMap<&str, &str> = BTreeMap::new();
+        let mut queue = VecDeque::from([from]);
+
+        while let Some(current) = queue.pop_front() {
+            if current == to {
+                let mut path = vec![to.to_string()];
+                let mut at = to;
+                while let Some(prev) = previous.get(at) {
+                    path.push(prev.to_string());
+                    at = prev;
+                }
+                path.reverse();
+                return Ok(path);
+            }
+
+            for next in self.edges[current].iter() {
+                if next != from && !previous.contains_key(next.as_str()) {
+                    previous.insert(next, current);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        Err(CategoryError::NoPath {
+            from: from.to_string(),
+            to: to.to_string(),
+        })
+    }
+}
+
+/// The map lists a value passes through on its way from `from` to `to`.
+pub fn find_stages<'a>(
+    almanac: &'a Almanac,
+    from: &str,
+    to: &str,
+) -> Result<Vec<&'a [Map]>, CategoryError> {
+    let path = CategoryGraph::from_almanac(almanac)?.path(from, to)?;
+
+    Ok(path
+        .windows(2)
+        .map(|pair| almanac.maps[&format!("{}-to-{}", pair[0], pair[1])].as_slice())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        almanac::{find_trace, parse_almanac},
+        categories::{find_stages, parse_header, CategoryError, CategoryGraph},
+    };
+
+    #[test]
+    fn it_parses_headers() {
+        assert_eq!(parse_header("seed-to-soil"), Ok(("seed", "soil")));
+        assert_eq!(
+            parse_header("seed-soil"),
+            Err(CategoryError::MalformedHeader("seed-soil".to_string()))
+        );
+        assert!(parse_header("-to-soil").is_err());
+    }
+
+    #[test]
+    fn it_finds_paths_between_categories() {
+        // water is reached directly, so fertilizer is skipped
+        let input = "
+        seeds: 1
+
+        seed-to-soil map:
+        10 0 5
+
+        soil-to-fertilizer map:
+        20 10 5
+
+        seed-to-water map:
+        30 0 5
+
+        water-to-light map:
+        40 30 5"
+            .trim();
+        let almanac = parse_almanac(input).unwrap();
+        let graph = CategoryGraph::from_almanac(&almanac).unwrap();
+
+        assert_eq!(
+            graph.categories().collect::<Vec<&str>>(),
+            vec!["fertilizer", "light", "seed", "soil", "water"]
+        );
+        assert_eq!(graph.edges().count(), 4);
+
+        assert_eq!(
+            graph.path("seed", "light").unwrap(),
+            vec!["seed", "water", "light"]
+        );
+        assert_eq!(graph.path("soil", "soil").unwrap(), vec!["soil"]);
+        assert_eq!(
+            find_stages(&almanac, "seed", "fertilizer").unwrap().len(),
+            2
+        );
+        assert_eq!(find_trace(&almanac, "seed", "light", 2), Ok(42));
+        assert_eq!(find_trace(&almanac, "seed", "fertilizer", 2), Ok(22));
+
+        assert_eq!(
+            graph.path("light", "seed"),
+            Err(CategoryError::NoPath {
+                from: "light".to_string(),
+                to: "seed".to_string()
+            })
+        );
+        assert_eq!(
+            graph.path("seed", "location"),
+            Err(CategoryError::UnknownCategory("location".to_string()))
+        );
+    }
+}
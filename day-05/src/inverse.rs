@@ -0,0 +1,130 @@
+use std::ops::Range;
+
+use crate::{
+    almanac::Almanac,
+    categories::{find_stages, CategoryError},
+    piecewise::PiecewiseMap,
+    ranges::merge_ranges,
+};
+
+/// Walks `locations` back through every category, humidity-to-location
+/// first, and returns the seeds that reach them as merged ranges.
+pub fn find_seeds_for_locations(
+    almanac: &Almanac,
+    locations: &[Range<u64>],
+) -> Result<Vec<Range<u64>>, CategoryError> {
+    Ok(find_stages(almanac, "seed", "location")?
+        .into_iter()
+        .rev()
+        .fold(merge_ranges(locations.to_vec()), |ranges, maps| {
+            let stage = PiecewiseMap::from_maps(maps);
+            merge_ranges(
+                ranges
+                    .iter()
+                    .flat_map(|range| stage.preimage(range))
+                    .collect(),
+            )
+        }))
+}
+
+pub fn find_seeds_for_location(
+    almanac: &Almanac,
+    location: u64,
+) -> Result<Vec<Range<u64>>, CategoryError> {
+    let location = location..location + 1;
+    find_seeds_for_locations(almanac, std::slice::from_ref(&location))
+}
+
+/// The lowest location of any seed in `seeds`, searched from the location side:
+/// the collapsed table's segments are visited in order of where they land, and
+/// the search stops once no segment can land lower than the best hit.
+pub fn find_lowest_location_by_inverse(
+    almanac: &Almanac,
+    seeds: &[Range<u64>],
+) -> Result<Option<u64>, CategoryError> {
+    let table = PiecewiseMap::from_almanac(almanac)?;
+    let seeds = merge_ranges(seeds.to_vec());
+
+    let mut segments = table.segments().to_vec();
+    segments.sort_by_key(|segment| segment.destination);
+
+    let mut best: Option<u64> = None;
+    for segment in segments.iter() {
+        if best.is_some_and(|best| segment.destination >= best) {
+            break;
+        }
+
+        // the first seed inside this segment lands lowest
+        let first = seeds
+            .iter()
+            .map(|range| range.start.max(segment.start)..range.end.min(segment.end))
+            .filter(|overlap| !overlap.is_empty())
+            .map(|overlap| overlap.start)
+            .min();
+
+        if let Some(seed) = first {
+            let location = segment.apply(seed);
+            best = Some(best.map_or(location, |best| best.min(location)));
+        }
+    }
+
+    Ok(best)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        almanac::{parse_almanac, parse_almanac_with, trace_stages, SeedMode, EXAMPLE},
+        categories::find_stages,
+        inverse::{
+            find_lowest_location_by_inverse, find_seeds_for_location, find_seeds_for_locations,
+        },
+        ranges::find_lowest_location,
+    };
+
+    #[test]
+    fn it_finds_the_seed_for_a_location() {
+        let almanac = parse_almanac(EXAMPLE.trim()).unwrap();
+
+        let seeds = find_seeds_for_location(&almanac, 82).unwrap();
+        assert_eq!(seeds.len(), 1);
+        assert_eq!(seeds[0], 79..80);
+
+        assert_eq!(
+            find_seeds_for_locations(&almanac, &[46..47, 82..83]),
+            Ok(vec![79..80, 82..83])
+        );
+    }
+
+    #[test]
+    fn it_inverts_every_location_range() {
+        let almanac = parse_almanac(EXAMPLE.trim()).unwrap();
+
+        let locations = 40..60;
+        let seeds = find_seeds_for_locations(&almanac, std::slice::from_ref(&locations)).unwrap();
+
+        // every seed found lands in the range, and every seed that lands there is found
+        let stages = find_stages(&almanac, "seed", "location").unwrap();
+        for seed in 0..200 {
+            let location = trace_stages(&stages, seed);
+            let found = seeds.iter().any(|range| range.contains(&seed));
+            assert_eq!(found, locations.contains(&location), "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn it_searches_from_the_location_side() {
+        let almanac = parse_almanac_with(EXAMPLE.trim(), SeedMode::Ranges).unwrap();
+        let ranges = almanac.seeds.clone();
+
+        assert_eq!(
+            find_lowest_location_by_inverse(&almanac, &ranges),
+            Ok(Some(46))
+        );
+        assert_eq!(
+            find_lowest_location_by_inverse(&almanac, &ranges),
+            find_lowest_location(&almanac, &ranges)
+        );
+        assert_eq!(find_lowest_location_by_inverse(&almanac, &[]), Ok(None));
+    }
+}
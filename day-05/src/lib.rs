@@ -0,0 +1,8 @@
+pub mod almanac;
+pub mod brute;
+pub mod categories;
+pub mod inverse;
+pub mod piecewise;
+pub mod ranges;
+pub mod report;
+pub mod validate;
@@ -0,0 +1,321 @@
+use std::{fmt, ops::Range};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    almanac::{Almanac, Map},
+    categories::{find_stages, CategoryError},
+};
+
+/// Sends `start..end` to `destination..destination + (end - start)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Segment {
+    pub start: u64,
+    pub end: u64,
+    pub destination: u64,
+}
+
+impl Segment {
+    pub fn len(&self) -> u64 {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.start == self.destination
+    }
+
+    // destinations past `u64::MAX` stop there, the same as `Map::apply`
+    pub fn apply(&self, val: u64) -> u64 {
+        self.destination.saturating_add(val - self.start)
+    }
+
+    pub fn destination_end(&self) -> u64 {
+        self.destination.saturating_add(self.len())
+    }
+}
+
+/// A whole chain of maps collapsed into one table: sorted, contiguous
+/// segments covering `0..u64::MAX`, with neighbours that continue each
+/// other merged. `u64::MAX` itself always maps to itself.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PiecewiseMap {
+    segments: Vec<Segment>,
+}
+
+impl PiecewiseMap {
+    pub fn identity() -> PiecewiseMap {
+        PiecewiseMap {
+            segments: vec![Segment {
+                start: 0,
+                end: u64::MAX,
+                destination: 0,
+            }],
+        }
+    }
+
+    /// One category as a table; where maps overlap the first one listed wins,
+    /// the same as `find_trace_seed_to_location`.
+    pub fn from_maps(maps: &[Map]) -> PiecewiseMap {
+        let mut bounds = vec![0, u64::MAX];
+        for map in maps.iter() {
+            bounds.push(map.source_range_start);
+            bounds.push(map.source_end());
+        }
+        bounds.sort();
+        bounds.dedup();
+
+        let segments = bounds
+            .windows(2)
+            .map(|pair| {
+                let destination = maps
+                    .iter()
+                    .find(|map| map.contains(pair[0]))
+                    .map(|map| map.apply(pair[0]))
+                    .unwrap_or(pair[0]);
+                Segment {
+                    start: pair[0],
+                    end: pair[1],
+                    destination,
+                }
+            })
+            .collect();
+
+        PiecewiseMap::merged(segments)
+    }
+
+    /// Every category of `almanac`, seed to location.
+    pub fn from_almanac(almanac: &Almanac) -> Result<PiecewiseMap, CategoryError> {
+        PiecewiseMap::between(almanac, "seed", "location")
+    }
+
+    /// The categories of `almanac` from `from` to `to` as one table.
+    pub fn between(almanac: &Almanac, from: &str, to: &str) -> Result<PiecewiseMap, CategoryError> {
+        Ok(find_stages(almanac, from, to)?
+            .into_iter()
+            .fold(PiecewiseMap::identity(), |f, maps| {
+                f.then(&PiecewiseMap::from_maps(maps))
+            }))
+    }
+
+    // joins neighbours where the second continues the first
+    fn merged(segments: Vec<Segment>) -> PiecewiseMap {
+        let mut out: Vec<Segment> = vec![];
+
+        for segment in segments.into_iter().filter(|s| !s.is_empty()) {
+            match out.last_mut() {
+                Some(last)
+                    if last.end == segment.start
+                        && last.destination_end() == segment.destination =>
+                {
+                    last.end = segment.end;
+                }
+                _ => out.push(segment),
+            }
+        }
+
+        PiecewiseMap { segments: out }
+    }
+
+    /// `self` followed by `next`.
+    pub fn then(&self, next: &PiecewiseMap) -> PiecewiseMap {
+        let mut segments: Vec<Segment> = vec![];
+
+        for segment in self.segments.iter() {
+            // walk the image of this segment through `next`
+            let mut start = segment.start;
+            while start < segment.end {
+                let image = segment.apply(start);
+                let Some(outer) = next.segment_for(image) else {
+                    // only u64::MAX is uncovered, and it maps to itself
+                    segments.push(Segment {
+                        start,
+                        end: segment.end,
+                        destination: image,
+                    });
+                    break;
+                };
+
+                let end = segment.end.min(start.saturating_add(outer.end - image));
+                segments.push(Segment {
+                    start,
+                    end,
+                    destination: outer.apply(image),
+                });
+                start = end;
+            }
+        }
+
+        PiecewiseMap::merged(segments)
+    }
+
+    fn segment_for(&self, val: u64) -> Option<&Segment> {
+        let i = self.segments.partition_point(|s| s.end <= val);
+        self.segments.get(i)
+    }
+
+    /// Binary search for the segment holding `val`.
+    pub fn eval(&self, val: u64) -> u64 {
+        match self.segment_for(val) {
+            Some(segment) => segment.apply(val),
+            None => val,
+        }
+    }
+
+    /// Every source range that lands inside `target`, sorted by start.
+    pub fn preimage(&self, target: &Range<u64>) -> Vec<Range<u64>> {
+        self.segments
+            .iter()
+            .filter_map(|segment| {
+                let start = segment.destination.max(target.start);
+                let end = segment.destination_end().min(target.end);
+                if start >= end {
+                    return None;
+                }
+                let source = segment.start + (start - segment.destination);
+                Some(source..source + (end - start))
+            })
+            .collect()
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<PiecewiseMap> {
+        serde_json::from_str(json)
+    }
+}
+
+/// One segment per line, e.g. `79..93 -> 81..95`, so two tables can be diffed.
+impl fmt::Display for PiecewiseMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for segment in self.segments.iter() {
+            writeln!(
+                f,
+                "{}..{} -> {}..{}",
+                segment.start,
+                segment.end,
+                segment.destination,
+                segment.destination_end()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        almanac::{parse_almanac, trace_stages, Map, EXAMPLE},
+        categories::find_stages,
+        piecewise::{PiecewiseMap, Segment},
+    };
+
+    #[test]
+    fn it_builds_one_category() {
+        let maps = vec![
+            Map {
+                destination_range_start: 50,
+                source_range_start: 98,
+                range_length: 2,
+            },
+            Map {
+                destination_range_start: 52,
+                source_range_start: 50,
+                range_length: 48,
+            },
+        ];
+
+        let f = PiecewiseMap::from_maps(&maps);
+
+        assert_eq!(
+            f.segments(),
+            &[
+                Segment {
+                    start: 0,
+                    end: 50,
+                    destination: 0,
+                },
+                Segment {
+                    start: 50,
+                    end: 98,
+                    destination: 52,
+                },
+                Segment {
+                    start: 98,
+                    end: 100,
+                    destination: 50,
+                },
+                Segment {
+                    start: 100,
+                    end: u64::MAX,
+                    destination: 100,
+                },
+            ]
+        );
+        assert_eq!(f.eval(99), 51);
+        assert_eq!(f.eval(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn it_matches_the_chain_everywhere() {
+        let almanac = parse_almanac(EXAMPLE.trim()).unwrap();
+        let f = PiecewiseMap::from_almanac(&almanac).unwrap();
+
+        let stages = find_stages(&almanac, "seed", "location").unwrap();
+        for seed in 0..200 {
+            assert_eq!(f.eval(seed), trace_stages(&stages, seed));
+        }
+        assert_eq!(f.eval(79), 82);
+    }
+
+    #[test]
+    fn it_prints_and_round_trips() {
+        let almanac = parse_almanac(EXAMPLE.trim()).unwrap();
+        let f = PiecewiseMap::from_almanac(&almanac).unwrap();
+
+        let printed = f.to_string();
+        assert_eq!(printed.lines().count(), f.segments().len());
+        // seeds 0 to 13 shift together, 13 lands on 35
+        assert!(printed.starts_with("0..14 -> 22..36\n"));
+
+        let json = f.to_json();
+        assert_eq!(PiecewiseMap::from_json(&json).unwrap(), f);
+    }
+
+    #[test]
+    fn it_stops_destinations_at_u64_max() {
+        // the parser accepts a map whose destinations run past u64::MAX
+        let input = "
+        seeds: 1
+
+        seed-to-location map:
+        18446744073709551610 0 10"
+            .trim();
+        let almanac = parse_almanac(input).unwrap();
+
+        let f = PiecewiseMap::from_almanac(&almanac).unwrap();
+
+        assert_eq!(f.eval(3), u64::MAX - 2);
+        assert_eq!(f.eval(5), u64::MAX);
+        assert_eq!(f.eval(9), u64::MAX);
+        assert_eq!(f.eval(10), 10);
+        // the identity past the map lands there too
+        assert_eq!(
+            f.preimage(&(u64::MAX - 5..u64::MAX - 3)),
+            vec![0..2, u64::MAX - 5..u64::MAX - 3]
+        );
+        assert_eq!(
+            f.to_string().lines().next(),
+            Some("0..10 -> 18446744073709551610..18446744073709551615")
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use std::ops::Range;
+
+use crate::{
+    almanac::{Almanac, Map},
+    categories::{find_stages, CategoryError},
+    piecewise::PiecewiseMap,
+};
+
+/// Pushes every range through one category, splitting it wherever a map
+/// starts or ends. Parts no map covers pass through unchanged, and where maps
+/// overlap the first one listed wins, as in `find_trace_seed_to_location`.
+pub fn map_ranges(ranges: &[Range<u64>], maps: &[Map]) -> Vec<Range<u64>> {
+    let stage = PiecewiseMap::from_maps(maps);
+
+    let mut mapped: Vec<Range<u64>> = vec![];
+
+    for range in ranges.iter() {
+        for segment in stage.segments().iter() {
+            let start = range.start.max(segment.start);
+            let end = range.end.min(segment.end);
+            if start < end {
+                mapped.push(segment.apply(start)..segment.apply(start).saturating_add(end - start));
+            }
+        }
+    }
+
+    mapped
+}
+
+/// Sorts `ranges` and joins any that overlap or touch.
+pub fn merge_ranges(mut ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
+    ranges.retain(|range| !range.is_empty());
+    ranges.sort_by_key(|range| range.start);
+
+    let mut merged: Vec<Range<u64>> = vec![];
+    for range in ranges.into_iter() {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+
+    merged
+}
+
+/// Every `to` value reachable from the `from` values in `ranges`, as ranges.
+pub fn find_ranges_between(
+    almanac: &Almanac,
+    from: &str,
+    to: &str,
+    ranges: &[Range<u64>],
+) -> Result<Vec<Range<u64>>, CategoryError> {
+    Ok(find_stages(almanac, from, to)?
+        .into_iter()
+        .fold(ranges.to_vec(), |ranges, maps| map_ranges(&ranges, maps)))
+}
+
+/// Every location reachable from `ranges`, as ranges.
+pub fn find_ranges_to_location(
+    almanac: &Almanac,
+    ranges: &[Range<u64>],
+) -> Result<Vec<Range<u64>>, CategoryError> {
+    find_ranges_between(almanac, "seed", "location", ranges)
+}
+
+/// The smallest location of any seed in `ranges`.
+pub fn find_lowest_location(
+    almanac: &Almanac,
+    ranges: &[Range<u64>],
+) -> Result<Option<u64>, CategoryError> {
+    Ok(find_ranges_to_location(almanac, ranges)?
+        .into_iter()
+        .filter(|range| !range.is_empty())
+        .map(|range| range.start)
+        .min())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        almanac::{parse_almanac, parse_almanac_with, trace_stages, Map, SeedMode, EXAMPLE},
+        categories::find_stages,
+        ranges::{find_lowest_location, find_ranges_to_location, map_ranges, merge_ranges},
+    };
+
+    #[test]
+    fn it_splits_ranges_at_map_boundaries() {
+        let maps = vec![
+            Map {
+                destination_range_start: 100,
+                source_range_start: 10,
+                range_length: 5,
+            },
+            Map {
+                destination_range_start: 0,
+                source_range_start: 20,
+                range_length: 5,
+            },
+        ];
+
+        // 5..10 passes through, 10..15 shifts, 15..20 passes, 20..22 shifts
+        let range = 5..22;
+        let actual = map_ranges(std::slice::from_ref(&range), &maps);
+
+        assert_eq!(actual, vec![5..10, 100..105, 15..20, 0..2]);
+        assert_eq!(map_ranges(&[30..40, 40..45], &maps), vec![30..40, 40..45]);
+    }
+
+    #[test]
+    fn it_resolves_overlaps_in_file_order() {
+        // 5..15 is claimed by both maps, the first one listed wins
+        let input = "
+        seeds: 5
+
+        seed-to-location map:
+        100 5 10
+        200 0 20"
+            .trim();
+        let almanac = parse_almanac(input).unwrap();
+
+        let locations = find_ranges_to_location(&almanac, &almanac.seeds).unwrap();
+        assert_eq!(locations, vec![100..101]);
+
+        let stages = find_stages(&almanac, "seed", "location").unwrap();
+        for seed in 0..25 {
+            let range = seed..seed + 1;
+            let locations =
+                find_ranges_to_location(&almanac, std::slice::from_ref(&range)).unwrap();
+            let expected = trace_stages(&stages, seed);
+            assert_eq!(locations, vec![expected..expected + 1], "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn it_finds_the_lowest_location() {
+        let almanac = parse_almanac_with(EXAMPLE.trim(), SeedMode::Ranges).unwrap();
+        let ranges = almanac.seeds.clone();
+
+        assert_eq!(ranges, vec![79..93, 55..68]);
+        assert_eq!(find_lowest_location(&almanac, &ranges), Ok(Some(46)));
+    }
+
+    #[test]
+    fn it_agrees_with_the_single_value_path() {
+        let almanac = parse_almanac(EXAMPLE.trim()).unwrap();
+
+        let stages = find_stages(&almanac, "seed", "location").unwrap();
+        for seed in 0..120 {
+            let range = seed..seed + 1;
+            let locations =
+                find_ranges_to_location(&almanac, std::slice::from_ref(&range)).unwrap();
+            let expected = trace_stages(&stages, seed);
+
+            assert_eq!(locations, vec![expected..expected + 1]);
+        }
+    }
+
+    #[test]
+    fn it_merges_ranges() {
+        let actual = merge_ranges(vec![10..20, 0..5, 5..7, 15..25, 30..30]);
+
+        assert_eq!(actual, vec![0..7, 10..25]);
+    }
+}
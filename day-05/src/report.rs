@@ -0,0 +1,419 @@
+use std::ops::Range;
+
+use crate::{
+    almanac::{Almanac, Map},
+    categories::{find_stages, CategoryError, CategoryGraph},
+    piecewise::{PiecewiseMap, Segment},
+    ranges::merge_ranges,
+};
+
+/// The values a set of seeds holds in one category.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathStep {
+    pub category: String,
+    pub ranges: Vec<Range<u64>>,
+}
+
+// the categories from seed to location and the maps between each pair
+fn find_chain(almanac: &Almanac) -> Result<(Vec<String>, Vec<&[Map]>), CategoryError> {
+    let categories = CategoryGraph::from_almanac(almanac)?.path("seed", "location")?;
+    let stages = find_stages(almanac, "seed", "location")?;
+    Ok((categories, stages))
+}
+
+/// Where `seeds` are in every category, seed first and location last.
+pub fn find_path(almanac: &Almanac, seeds: &[Range<u64>]) -> Result<Vec<PathStep>, CategoryError> {
+    let (categories, stages) = find_chain(almanac)?;
+
+    let mut ranges = merge_ranges(seeds.to_vec());
+    let mut path = vec![];
+    for (i, category) in categories.into_iter().enumerate() {
+        if i > 0 {
+            let stage = PiecewiseMap::from_maps(stages[i - 1]);
+            ranges = merge_ranges(
+                pieces(&stage, &ranges)
+                    .into_iter()
+                    .map(|(_, destination)| destination)
+                    .collect(),
+            );
+        }
+        path.push(PathStep {
+            category,
+            ranges: ranges.clone(),
+        });
+    }
+
+    Ok(path)
+}
+
+// each part of `ranges` that one segment of `stage` moves, and where it lands
+fn pieces(stage: &PiecewiseMap, ranges: &[Range<u64>]) -> Vec<(Range<u64>, Range<u64>)> {
+    let mut out = vec![];
+    for range in ranges.iter() {
+        for segment in stage.segments().iter() {
+            let start = range.start.max(segment.start);
+            let end = range.end.min(segment.end);
+            if start < end {
+                out.push((
+                    start..end,
+                    segment.apply(start)..segment.apply(start).saturating_add(end - start),
+                ));
+            }
+        }
+    }
+    out
+}
+
+fn format_ranges(ranges: &[Range<u64>]) -> String {
+    if ranges.is_empty() {
+        return "-".to_string();
+    }
+
+    ranges
+        .iter()
+        .map(|range| match range.end - range.start {
+            1 => range.start.to_string(),
+            _ => format!("{}..{}", range.start, range.end),
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+fn format_shift(map: &Map) -> String {
+    let shift = map.destination_range_start as i128 - map.source_range_start as i128;
+    match shift {
+        0 => "0".to_string(),
+        _ => format!("{:+}", shift),
+    }
+}
+
+// right aligned columns, `|` between them
+fn format_table(rows: &[Vec<String>]) -> String {
+    let columns = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let widths = (0..columns)
+        .map(|i| rows.iter().map(|row| row[i].len()).max().unwrap())
+        .collect::<Vec<usize>>();
+
+    let mut out = String::new();
+    for row in rows.iter() {
+        let line = row
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, width)| format!("{:>width$}", cell, width = width))
+            .collect::<Vec<String>>()
+            .join(" | ");
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// Every map of one category, in file order.
+pub fn render_stage_table(maps: &[Map]) -> String {
+    let header = ["#", "source", "destination", "length", "shift"].map(|f| f.to_string());
+
+    let rows = maps.iter().enumerate().map(|(i, map)| {
+        vec![
+            i.to_string(),
+            format!(
+                "{}..{}",
+                map.source_range_start,
+                map.source_range_start.saturating_add(map.range_length)
+            ),
+            format!(
+                "{}..{}",
+                map.destination_range_start,
+                map.destination_range_start.saturating_add(map.range_length)
+            ),
+            map.range_length.to_string(),
+            format_shift(map),
+        ]
+    });
+
+    format_table(
+        &[header.to_vec()]
+            .into_iter()
+            .chain(rows)
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// A table per category, seed-to-soil first, each under its map name.
+pub fn render_tables(almanac: &Almanac) -> Result<String, CategoryError> {
+    let (categories, stages) = find_chain(almanac)?;
+
+    let mut out = String::new();
+    for (pair, maps) in categories.windows(2).zip(stages) {
+        out.push_str(&format!("{}-to-{}\n", pair[0], pair[1]));
+        out.push_str(&render_stage_table(maps));
+        out.push('\n');
+    }
+
+    Ok(out)
+}
+
+/// The values `seeds` take in every category, one category per line.
+pub fn render_path(almanac: &Almanac, seeds: &[Range<u64>]) -> Result<String, CategoryError> {
+    let rows = find_path(almanac, seeds)?
+        .into_iter()
+        .map(|step| vec![step.category, format_ranges(&step.ranges)])
+        .collect::<Vec<Vec<String>>>();
+
+    // category names read better left aligned
+    let width = rows.iter().map(|row| row[0].len()).max().unwrap_or(0);
+    Ok(rows
+        .into_iter()
+        .map(|row| format!("{:<width$}  {}\n", row[0], row[1], width = width))
+        .collect())
+}
+
+// category names come straight from the almanac's headers
+fn escape_xml(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '&' => "&amp;".to_string(),
+            '<' => "&lt;".to_string(),
+            '>' => "&gt;".to_string(),
+            '"' => "&quot;".to_string(),
+            _ => c.to_string(),
+        })
+        .collect()
+}
+
+const COLUMN_GAP: f64 = 160.0;
+const PLOT_HEIGHT: f64 = 400.0;
+const MARGIN: f64 = 40.0;
+
+/// An SVG with an axis per category, seed on the left. Each band joins a
+/// source interval to where it lands in the next category, grey where values
+/// pass through unchanged; `seeds`, if any, are drawn over the top in red.
+pub fn render_svg(almanac: &Almanac, seeds: &[Range<u64>]) -> Result<String, CategoryError> {
+    let (categories, stages) = find_chain(almanac)?;
+
+    // every axis runs from 0 to the largest value any map or seed mentions
+    let top = stages
+        .iter()
+        .flat_map(|maps| maps.iter())
+        .flat_map(|map| {
+            [
+                map.source_range_start.saturating_add(map.range_length),
+                map.destination_range_start.saturating_add(map.range_length),
+            ]
+        })
+        .chain(seeds.iter().map(|range| range.end))
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    let x = |i: usize| MARGIN + i as f64 * COLUMN_GAP;
+    let y = |val: u64| MARGIN + val as f64 / top as f64 * PLOT_HEIGHT;
+    let band = |i: usize, source: &Range<u64>, destination: &Range<u64>, class: &str| {
+        format!(
+            "<polygon class=\"{}\" points=\"{:.1},{:.1} {:.1},{:.1} {:.1},{:.1} {:.1},{:.1}\"/>\n",
+            class,
+            x(i),
+            y(source.start),
+            x(i),
+            y(source.end),
+            x(i + 1),
+            y(destination.end),
+            x(i + 1),
+            y(destination.start)
+        )
+    };
+
+    let width = 2.0 * MARGIN + (categories.len().max(1) - 1) as f64 * COLUMN_GAP;
+    let height = 2.0 * MARGIN + PLOT_HEIGHT;
+
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.0}\" height=\"{:.0}\">
+<style>
+.shift {{ fill: #3070c0; fill-opacity: 0.5; }}
+.identity {{ fill: #999999; fill-opacity: 0.3; }}
+.path {{ fill: #d02020; fill-opacity: 0.8; }}
+.axis {{ stroke: #333333; }}
+text {{ font: 11px sans-serif; text-anchor: middle; }}
+</style>
+",
+        width, height
+    );
+
+    for (i, maps) in stages.iter().enumerate() {
+        let stage = PiecewiseMap::from_maps(maps);
+        let visible = stage
+            .segments()
+            .iter()
+            .filter(|segment| segment.start < top)
+            .map(|segment| Segment {
+                end: segment.end.min(top),
+                ..*segment
+            });
+        for segment in visible {
+            let class = if segment.is_identity() {
+                "identity"
+            } else {
+                "shift"
+            };
+            out.push_str(&band(
+                i,
+                &(segment.start..segment.end),
+                &(segment.destination..segment.destination_end()),
+                class,
+            ));
+        }
+    }
+
+    let path = find_path(almanac, seeds)?;
+    for (i, step) in path.iter().enumerate().take(stages.len()) {
+        let stage = PiecewiseMap::from_maps(stages[i]);
+        for (source, destination) in pieces(&stage, &step.ranges) {
+            out.push_str(&band(i, &source, &destination, "path"));
+        }
+    }
+
+    for (i, category) in categories.iter().enumerate() {
+        out.push_str(&format!(
+            "<line class=\"axis\" x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\"/>\n",
+            x(i),
+            y(0),
+            x(i),
+            y(top)
+        ));
+        out.push_str(&format!(
+            "<text x=\"{:.1}\" y=\"{:.1}\">{}</text>\n",
+            x(i),
+            MARGIN / 2.0,
+            escape_xml(category)
+        ));
+    }
+    out.push_str(&format!(
+        "<text x=\"{:.1}\" y=\"{:.1}\">{}</text>\n",
+        MARGIN,
+        height - MARGIN / 4.0,
+        top
+    ));
+
+    out.push_str("</svg>\n");
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        almanac::{parse_almanac, EXAMPLE},
+        report::{find_path, render_path, render_stage_table, render_svg, render_tables, PathStep},
+    };
+
+    #[test]
+    fn it_renders_stage_tables() {
+        let almanac = parse_almanac(EXAMPLE.trim()).unwrap();
+
+        assert_eq!(
+            render_stage_table(&almanac.maps["seed-to-soil"]),
+            "# |  source | destination | length | shift
+0 | 98..100 |      50..52 |      2 |   -48
+1 |  50..98 |     52..100 |     48 |    +2
+"
+        );
+
+        let tables = render_tables(&almanac).unwrap();
+        assert!(tables.starts_with("seed-to-soil\n"));
+        assert!(tables.contains("\nhumidity-to-location\n"));
+    }
+
+    #[test]
+    fn it_follows_a_seed_through_every_category() {
+        // the puzzle's walkthrough of seed 79
+        let almanac = parse_almanac(EXAMPLE.trim()).unwrap();
+
+        let seed = 79..80;
+        let path = find_path(&almanac, std::slice::from_ref(&seed)).unwrap();
+        let values = path
+            .iter()
+            .map(|step| (step.category.as_str(), step.ranges[0].start))
+            .collect::<Vec<(&str, u64)>>();
+
+        assert_eq!(
+            values,
+            vec![
+                ("seed", 79),
+                ("soil", 81),
+                ("fertilizer", 81),
+                ("water", 81),
+                ("light", 74),
+                ("temperature", 78),
+                ("humidity", 78),
+                ("location", 82),
+            ]
+        );
+
+        // 55..68 is split up on the way to location
+        let seeds = 55..68;
+        let last = find_path(&almanac, std::slice::from_ref(&seeds))
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert_eq!(
+            last,
+            PathStep {
+                category: "location".to_string(),
+                ranges: vec![56..60, 86..90, 94..99],
+            }
+        );
+
+        let text = render_path(&almanac, std::slice::from_ref(&seed)).unwrap();
+        assert_eq!(text.lines().next(), Some("seed         79"));
+        assert_eq!(text.lines().last(), Some("location     82"));
+    }
+
+    #[test]
+    fn it_renders_svg() {
+        let almanac = parse_almanac(EXAMPLE.trim()).unwrap();
+
+        let seed = 79..80;
+        let svg = render_svg(&almanac, std::slice::from_ref(&seed)).unwrap();
+
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<line class=\"axis\"").count(), 8);
+        assert_eq!(svg.matches("class=\"path\"").count(), 7);
+        assert!(svg.contains(">humidity</text>"));
+    }
+
+    #[test]
+    fn it_draws_destinations_up_to_u64_max() {
+        // the parser accepts a map whose destinations run past u64::MAX
+        let input = "
+        seeds: 1
+
+        seed-to-location map:
+        18446744073709551610 0 10"
+            .trim();
+        let almanac = parse_almanac(input).unwrap();
+
+        let svg = render_svg(&almanac, &[]).unwrap();
+
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("class=\"shift\"").count(), 1);
+        assert!(svg.contains(">18446744073709551615</text>"));
+    }
+
+    #[test]
+    fn it_escapes_category_names_in_svg() {
+        let input = "
+        seeds: 1
+
+        seed-to-<soil> & \"dirt\" map:
+        5 0 10
+
+        <soil> & \"dirt\"-to-location map:
+        0 5 10"
+            .trim();
+        let almanac = parse_almanac(input).unwrap();
+
+        let svg = render_svg(&almanac, &[]).unwrap();
+
+        assert!(svg.contains(">&lt;soil&gt; &amp; &quot;dirt&quot;</text>"));
+        assert!(!svg.contains("<soil>"));
+    }
+}
@@ -0,0 +1,269 @@
+use std::{fmt, ops::Range};
+
+use crate::almanac::{Almanac, Map};
+
+/// Something suspicious about one map list; maps are numbered in file order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    // two maps claim the same sources, only the first one listed is ever used
+    OverlappingSources {
+        name: String,
+        first: usize,
+        second: usize,
+    },
+    // two maps send values to the same destinations, so the map is not injective
+    OverlappingDestinations {
+        name: String,
+        first: usize,
+        second: usize,
+    },
+    // sources between two maps that no map covers, they pass through unchanged
+    Gap {
+        name: String,
+        range: Range<u64>,
+    },
+    // the source or destination end of a map is past `u64::MAX`
+    Overflow {
+        name: String,
+        index: usize,
+    },
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::OverlappingSources {
+                name,
+                first,
+                second,
+            } => write!(
+                f,
+                "{}: maps {} and {} overlap in their sources",
+                name, first, second
+            ),
+            Issue::OverlappingDestinations {
+                name,
+                first,
+                second,
+            } => write!(
+                f,
+                "{}: maps {} and {} overlap in their destinations",
+                name, first, second
+            ),
+            Issue::Gap { name, range } => {
+                write!(f, "{}: no map covers {}..{}", name, range.start, range.end)
+            }
+            Issue::Overflow { name, index } => {
+                write!(f, "{}: map {} runs past u64::MAX", name, index)
+            }
+        }
+    }
+}
+
+/// What `check_almanac` does with the issues it finds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ValidationMode {
+    /// hand the issues back as warnings
+    #[default]
+    Lenient,
+    /// reject the almanac if there are any
+    Strict,
+}
+
+/// The issues that made a strict check reject an almanac.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidAlmanac(pub Vec<Issue>);
+
+impl fmt::Display for InvalidAlmanac {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "almanac has {} issue(s)", self.0.len())?;
+        for issue in self.0.iter() {
+            write!(f, "\n  {}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for InvalidAlmanac {}
+
+// None when the range does not fit in a u64
+fn span(start: u64, len: u64) -> Option<Range<u64>> {
+    start.checked_add(len).map(|end| start..end)
+}
+
+fn overlaps(a: &Range<u64>, b: &Range<u64>) -> bool {
+    a.start < b.end && b.start < a.end
+}
+
+fn validate_maps(name: &str, maps: &[Map]) -> Vec<Issue> {
+    let mut issues: Vec<Issue> = vec![];
+
+    // maps that overflow are reported once and left out of the other checks
+    let mut spans: Vec<(usize, Range<u64>, Range<u64>)> = vec![];
+    for (index, map) in maps.iter().enumerate() {
+        match (
+            span(map.source_range_start, map.range_length),
+            span(map.destination_range_start, map.range_length),
+        ) {
+            (Some(source), Some(destination)) => spans.push((index, source, destination)),
+            _ => issues.push(Issue::Overflow {
+                name: name.to_string(),
+                index,
+            }),
+        }
+    }
+
+    for (i, (first, source_a, destination_a)) in spans.iter().enumerate() {
+        for (second, source_b, destination_b) in spans[i + 1..].iter() {
+            if overlaps(source_a, source_b) {
+                issues.push(Issue::OverlappingSources {
+                    name: name.to_string(),
+                    first: *first,
+                    second: *second,
+                });
+            }
+            if overlaps(destination_a, destination_b) {
+                issues.push(Issue::OverlappingDestinations {
+                    name: name.to_string(),
+                    first: *first,
+                    second: *second,
+                });
+            }
+        }
+    }
+
+    let mut sources = spans
+        .into_iter()
+        .map(|(_, source, _)| source)
+        .filter(|source| !source.is_empty())
+        .collect::<Vec<Range<u64>>>();
+    sources.sort_by_key(|source| source.start);
+
+    let mut covered_to: Option<u64> = None;
+    for source in sources.into_iter() {
+        if let Some(end) = covered_to.filter(|end| *end < source.start) {
+            issues.push(Issue::Gap {
+                name: name.to_string(),
+                range: end..source.start,
+            });
+        }
+        covered_to = Some(covered_to.map_or(source.end, |end| end.max(source.end)));
+    }
+
+    issues
+}
+
+/// Overflow, overlaps and gaps in every map list, ordered by map name.
+pub fn validate_almanac(almanac: &Almanac) -> Vec<Issue> {
+    let mut names = almanac.maps.keys().collect::<Vec<&String>>();
+    names.sort();
+
+    names
+        .into_iter()
+        .flat_map(|name| validate_maps(name, &almanac.maps[name]))
+        .collect()
+}
+
+pub fn check_almanac(
+    almanac: &Almanac,
+    mode: ValidationMode,
+) -> Result<Vec<Issue>, InvalidAlmanac> {
+    let issues = validate_almanac(almanac);
+
+    match mode {
+        ValidationMode::Strict if !issues.is_empty() => Err(InvalidAlmanac(issues)),
+        _ => Ok(issues),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        almanac::{find_trace, parse_almanac},
+        validate::{check_almanac, validate_almanac, InvalidAlmanac, Issue, ValidationMode},
+    };
+
+    #[test]
+    fn it_accepts_a_clean_almanac() {
+        let input = "
+        seeds: 79 14 55 13
+
+        seed-to-soil map:
+        50 98 2
+        52 50 48"
+            .trim();
+        let almanac = parse_almanac(input).unwrap();
+
+        assert!(validate_almanac(&almanac).is_empty());
+        assert_eq!(check_almanac(&almanac, ValidationMode::Strict), Ok(vec![]));
+    }
+
+    #[test]
+    fn it_reports_overlaps_gaps_and_overflow() {
+        // map 1 shadows part of map 0 and lands on the same values as map 2,
+        // 20..40 is uncovered and map 3 runs off the end
+        let input = "
+        seeds: 1
+
+        seed-to-soil map:
+        100 0 20
+        200 10 10
+        205 40 5
+        0 18446744073709551610 10"
+            .trim();
+        let almanac = parse_almanac(input).unwrap();
+        let name = || "seed-to-soil".to_string();
+
+        let expected = vec![
+            Issue::Overflow {
+                name: name(),
+                index: 3,
+            },
+            Issue::OverlappingSources {
+                name: name(),
+                first: 0,
+                second: 1,
+            },
+            Issue::OverlappingDestinations {
+                name: name(),
+                first: 1,
+                second: 2,
+            },
+            Issue::Gap {
+                name: name(),
+                range: 20..40,
+            },
+        ];
+
+        assert_eq!(validate_almanac(&almanac), expected);
+        assert_eq!(
+            check_almanac(&almanac, ValidationMode::Lenient),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            check_almanac(&almanac, ValidationMode::Strict),
+            Err(InvalidAlmanac(expected.clone()))
+        );
+        assert_eq!(
+            expected[3].to_string(),
+            "seed-to-soil: no map covers 20..40"
+        );
+    }
+
+    #[test]
+    fn it_traces_leniently_through_overflowing_maps() {
+        // the map's source end is cut short at u64::MAX
+        let input = "
+        seeds: 1
+
+        seed-to-soil map:
+        0 18446744073709551610 10"
+            .trim();
+        let almanac = parse_almanac(input).unwrap();
+
+        assert!(check_almanac(&almanac, ValidationMode::Lenient).is_ok());
+        assert_eq!(find_trace(&almanac, "seed", "soil", 5), Ok(5));
+        assert_eq!(find_trace(&almanac, "seed", "soil", u64::MAX - 3), Ok(2));
+        assert_eq!(find_trace(&almanac, "seed", "soil", u64::MAX), Ok(u64::MAX));
+    }
+}